@@ -0,0 +1,9 @@
+//! Artifact upload and retrieval, with content sealed at rest under the
+//! owning tenant's envelope-encryption DEK (see [`crate::crypto`]).
+//! Metadata (filename, scores, grants) lives unencrypted; only the
+//! uploaded bytes themselves are sealed.
+
+mod routes;
+mod storage;
+
+pub use routes::router;
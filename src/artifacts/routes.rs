@@ -0,0 +1,71 @@
+use axum::extract::{Multipart, Path, State};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::storage::{download, upload};
+use crate::auth::AuthenticatedUser;
+use crate::error::{Error, Result};
+use crate::state::AppState;
+
+#[derive(Debug, Serialize)]
+struct UploadResponse {
+    artifact_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactResponse {
+    artifact_id: Uuid,
+    tenant_id: Uuid,
+    filename: String,
+    size_bytes: usize,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/artifacts/upload", post(upload_handler))
+        .route("/api/v1/artifacts/:artifact_id", get(get_artifact_handler))
+}
+
+async fn upload_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Json<UploadResponse>> {
+    user.require_scope("artifact:write")?;
+
+    let field = multipart
+        .next_field()
+        .await
+        .map_err(|e| Error::BadRequest(format!("invalid multipart body: {e}")))?
+        .ok_or_else(|| Error::BadRequest("missing \"file\" field".to_string()))?;
+
+    let filename = field.file_name().unwrap_or("upload").to_string();
+    let bytes = field
+        .bytes()
+        .await
+        .map_err(|e| Error::BadRequest(format!("failed to read uploaded file: {e}")))?;
+
+    let artifact_id = upload(&state.db, &state.kek, user.tenant_id, filename, bytes.to_vec()).await?;
+
+    Ok(Json(UploadResponse { artifact_id }))
+}
+
+async fn get_artifact_handler(
+    user: AuthenticatedUser,
+    Path(artifact_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<ArtifactResponse>> {
+    user.require_scope_for_resource("artifact:read", artifact_id)?;
+
+    let (metadata, plaintext) =
+        download(&state.db, &state.kek, &state.sharing, user.tenant_id, artifact_id).await?;
+
+    Ok(Json(ArtifactResponse {
+        artifact_id: metadata.id,
+        tenant_id: metadata.tenant_id,
+        filename: metadata.filename,
+        size_bytes: plaintext.len(),
+    }))
+}
@@ -0,0 +1,179 @@
+use uuid::Uuid;
+
+use crate::crypto::{current_tenant_dek, open_and_maybe_reseal, Kek, SealedBlob, TenantDek};
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+use crate::sharing::UmbralKeyRing;
+
+/// An artifact's non-sensitive metadata, as stored alongside its sealed
+/// blob.
+pub struct ArtifactMetadata {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub filename: String,
+}
+
+/// Seals `plaintext` under `tenant_id`'s current DEK and stores it as a
+/// new artifact row.
+pub async fn upload(
+    db: &TenantScopedPool,
+    kek: &Kek,
+    tenant_id: Uuid,
+    filename: String,
+    plaintext: Vec<u8>,
+) -> Result<Uuid> {
+    let kek = kek.clone();
+    let artifact_id = Uuid::new_v4();
+
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(async move {
+            let (dek, key_version) = current_tenant_dek(tx, &kek, tenant_id).await?;
+            let sealed = dek.seal(tenant_id, artifact_id, key_version, &plaintext)?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO artifacts (id, tenant_id, filename, ciphertext, nonce, key_version)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+                artifact_id,
+                tenant_id,
+                filename,
+                sealed.ciphertext,
+                sealed.nonce,
+                sealed.key_version,
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            Ok(())
+        })
+    })
+    .await?;
+
+    Ok(artifact_id)
+}
+
+/// Loads `artifact_id` and opens it, either under `tenant_id`'s own DEK
+/// (if `tenant_id` is the owner, rotating it onto the tenant's current
+/// key version the first time anything reads it after a rotation) or, if
+/// `tenant_id` was granted access by the owner (see `crate::sharing`), by
+/// recovering the DEK the grant re-wrapped for it. A grantee never
+/// triggers a reseal -- it has no `UPDATE` access to the owner's artifact
+/// row, only the `SELECT` access `grant_based_artifact_access` grants it.
+pub async fn download(
+    db: &TenantScopedPool,
+    kek: &Kek,
+    keyring: &UmbralKeyRing,
+    tenant_id: Uuid,
+    artifact_id: Uuid,
+) -> Result<(ArtifactMetadata, Vec<u8>)> {
+    let kek = kek.clone();
+    let keyring = keyring.clone();
+
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(async move {
+            let row = sqlx::query!(
+                r#"
+                SELECT tenant_id, filename, ciphertext, nonce, key_version
+                FROM artifacts
+                WHERE id = $1
+                "#,
+                artifact_id
+            )
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or(Error::NotFound)?;
+
+            let (ciphertext, nonce, key_version) = match (row.ciphertext, row.nonce, row.key_version) {
+                (Some(ciphertext), Some(nonce), Some(key_version)) => (ciphertext, nonce, key_version),
+                _ => {
+                    return Err(Error::BadRequest(
+                        "artifact has no sealed content".to_string(),
+                    ))
+                }
+            };
+
+            let sealed = SealedBlob {
+                key_version,
+                nonce,
+                ciphertext,
+            };
+
+            let plaintext = if row.tenant_id == tenant_id {
+                let (current_dek, current_version) = current_tenant_dek(tx, &kek, row.tenant_id).await?;
+                let (plaintext, resealed) = if sealed.key_version == current_version {
+                    (current_dek.open(row.tenant_id, artifact_id, &sealed)?, None)
+                } else {
+                    let sealing_dek =
+                        tenant_dek_for_version(tx, &kek, row.tenant_id, sealed.key_version).await?;
+                    open_and_maybe_reseal(
+                        &sealing_dek,
+                        &current_dek,
+                        current_version,
+                        row.tenant_id,
+                        artifact_id,
+                        &sealed,
+                    )?
+                };
+
+                if let Some(resealed) = resealed {
+                    sqlx::query!(
+                        r#"
+                        UPDATE artifacts
+                        SET ciphertext = $2, nonce = $3, key_version = $4
+                        WHERE id = $1
+                        "#,
+                        artifact_id,
+                        resealed.ciphertext,
+                        resealed.nonce,
+                        resealed.key_version,
+                    )
+                    .execute(&mut **tx)
+                    .await?;
+                }
+
+                plaintext
+            } else {
+                let grant_dek = crate::sharing::recover_grant_key(tx, &keyring, artifact_id, tenant_id).await?;
+                grant_dek.open(row.tenant_id, artifact_id, &sealed)?
+            };
+
+            Ok((
+                ArtifactMetadata {
+                    id: artifact_id,
+                    tenant_id: row.tenant_id,
+                    filename: row.filename,
+                },
+                plaintext,
+            ))
+        })
+    })
+    .await
+}
+
+/// Loads and unwraps the DEK for `tenant_id` at a specific past
+/// `key_version`, for opening artifacts sealed before the tenant's most
+/// recent rotation.
+async fn tenant_dek_for_version(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    kek: &Kek,
+    tenant_id: Uuid,
+    key_version: i32,
+) -> Result<TenantDek> {
+    let row = sqlx::query!(
+        r#"
+        SELECT wrapped_dek, wrap_nonce
+        FROM tenant_data_keys
+        WHERE tenant_id = $1 AND key_version = $2
+        "#,
+        tenant_id,
+        key_version
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    kek.unwrap(&crate::crypto::WrappedDek {
+        nonce: row.wrap_nonce,
+        ciphertext: row.wrapped_dek,
+    })
+}
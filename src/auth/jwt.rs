@@ -0,0 +1,102 @@
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// Claims carried by a Sentinel access token.
+///
+/// `scopes` follows a `resource:action` convention (`artifact:read`,
+/// `artifact:write`, `verdict:read`, `case:write`, ...); a route declares the
+/// scope it needs and checks it with [`AuthenticatedUser::require_scope`](super::AuthenticatedUser::require_scope).
+///
+/// `jti` identifies this token uniquely so it can be named in the
+/// [`revocation`](super::revocation) set without revoking every token the
+/// same user holds -- logging out one session, or rotating a compromised
+/// refresh token family, must not log out the others.
+///
+/// `mfa_complete` is false only for the partial token handed back by a
+/// login that still needs a second factor (see [`issue_partial_token`]);
+/// it carries no scopes, and [`AuthenticatedUser::require_scope`](super::AuthenticatedUser::require_scope)
+/// rejects it for anything privileged regardless.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: Uuid,
+    pub tenant_id: Uuid,
+    pub roles: Vec<String>,
+    pub scopes: Vec<String>,
+    pub jti: Uuid,
+    pub mfa_complete: bool,
+    pub exp: i64,
+}
+
+/// HS256 signing secret. Production deployments must override `JWT_SECRET`;
+/// the fallback only exists so the server boots in local/dev environments.
+fn signing_key() -> String {
+    std::env::var("JWT_SECRET").unwrap_or_else(|_| "sentinel-dev-secret".to_string())
+}
+
+pub fn issue_token(
+    user_id: Uuid,
+    tenant_id: Uuid,
+    roles: Vec<String>,
+    scopes: Vec<String>,
+) -> Result<String> {
+    issue_token_with_ttl(user_id, tenant_id, roles, scopes, true, Duration::hours(12))
+}
+
+/// Mints the partial token a login hands back when the user has a second
+/// factor enrolled: no roles, no scopes, `mfa_complete: false`, and only
+/// long enough to redeem at one of [`super::mfa`]'s verify endpoints
+/// before it has to be re-requested with a fresh sign-in.
+pub fn issue_partial_token(user_id: Uuid, tenant_id: Uuid) -> Result<String> {
+    issue_token_with_ttl(
+        user_id,
+        tenant_id,
+        Vec::new(),
+        Vec::new(),
+        false,
+        Duration::minutes(5),
+    )
+}
+
+/// Like [`issue_token`], but with an explicit lifetime and MFA-completion
+/// state instead of the standard fully-authenticated 12-hour session --
+/// used for narrower, shorter-lived delegated tokens (see
+/// [`super::delegation`]) and for [`issue_partial_token`].
+pub fn issue_token_with_ttl(
+    user_id: Uuid,
+    tenant_id: Uuid,
+    roles: Vec<String>,
+    scopes: Vec<String>,
+    mfa_complete: bool,
+    ttl: Duration,
+) -> Result<String> {
+    let claims = Claims {
+        sub: user_id,
+        tenant_id,
+        roles,
+        scopes,
+        jti: Uuid::new_v4(),
+        mfa_complete,
+        exp: (Utc::now() + ttl).timestamp(),
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(signing_key().as_bytes()),
+    )
+    .map_err(|e| Error::Internal(e.into()))
+}
+
+pub fn verify_token(token: &str) -> Result<Claims> {
+    decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(signing_key().as_bytes()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+    .map_err(|_| Error::Unauthorized("invalid or expired token".to_string()))
+}
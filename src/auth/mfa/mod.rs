@@ -0,0 +1,47 @@
+//! Second-factor authentication: TOTP ([`totp`], RFC 6238) and
+//! WebAuthn/FIDO2 ([`webauthn`]).
+//!
+//! A user with no factor enrolled signs in normally through
+//! [`crate::identity::login`]. One with a factor enrolled gets back a
+//! partial token instead (see [`super::issue_partial_token`]) -- empty
+//! scopes, `mfa_complete: false` -- which [`super::AuthenticatedUser::require_scope`]
+//! already refuses for anything privileged. [`router`] exposes the
+//! enroll/verify endpoints that redeem that partial token for a real
+//! session once the right code or assertion is presented.
+
+mod routes;
+mod totp;
+mod webauthn;
+
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+pub use routes::router;
+
+use crate::error::Result;
+
+/// Whether `user_id` has any second factor enrolled. `login` consults
+/// this to decide whether a successful password check alone is enough to
+/// issue a full session, or whether it must hand back a partial token
+/// pending one of this module's verify endpoints.
+pub async fn is_enrolled(tx: &mut Transaction<'_, Postgres>, user_id: Uuid) -> Result<bool> {
+    let has_totp = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM totp_credentials WHERE user_id = $1) AS "exists!""#,
+        user_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    if has_totp {
+        return Ok(true);
+    }
+
+    let has_webauthn = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM webauthn_credentials WHERE user_id = $1) AS "exists!""#,
+        user_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(has_webauthn)
+}
@@ -0,0 +1,198 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{totp, webauthn};
+use crate::auth::{issue_token, refresh, scopes_for_roles, AuthenticatedUser, TokenPair};
+use crate::error::{Error, Result};
+use crate::state::AppState;
+
+#[derive(Debug, Serialize)]
+pub struct TotpEnrollResponse {
+    /// Hex-encoded shared secret for the caller to provision into an
+    /// authenticator app. There's no `otpauth://` QR flow here -- this is
+    /// a backend, not a console -- so a raw hex secret is consistent with
+    /// how this codebase already hands out other secret material (see
+    /// the wrapped-DEK encoding in [`crate::crypto`]).
+    pub secret: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TotpVerifyRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebauthnRegisterRequest {
+    pub credential_id: String,
+    pub public_key: String,
+    pub algorithm: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebauthnVerifyRequest {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    pub counter: i64,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/auth/mfa/totp/enroll", post(totp_enroll_handler))
+        .route("/api/v1/auth/mfa/totp/verify", post(totp_verify_handler))
+        .route(
+            "/api/v1/auth/mfa/webauthn/register",
+            post(webauthn_register_handler),
+        )
+        .route("/api/v1/auth/mfa/webauthn/verify", post(webauthn_verify_handler))
+}
+
+/// Enrolls TOTP for the caller, replacing any prior secret. Requires an
+/// already fully-authenticated session -- enrolling a *new* factor is
+/// itself a privileged action, same as any other `require_scope`-gated
+/// route would be once one is already in place.
+async fn totp_enroll_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+) -> Result<Json<TotpEnrollResponse>> {
+    user.require_mfa_complete()?;
+    let secret = totp::generate_secret();
+
+    state
+        .db
+        .with_tenant(user.tenant_id, |tx| {
+            let secret = secret.clone();
+            Box::pin(async move { totp::enroll(tx, user.tenant_id, user.user_id, &secret).await })
+        })
+        .await?;
+
+    Ok(Json(TotpEnrollResponse {
+        secret: hex::encode(secret),
+    }))
+}
+
+/// Redeems a partial token for a full session by presenting a valid TOTP
+/// code. Deliberately does *not* call `require_scope` -- the whole point
+/// is that a partial token, which carries no scopes, can still reach
+/// this one endpoint.
+async fn totp_verify_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Json(body): Json<TotpVerifyRequest>,
+) -> Result<Json<TokenPair>> {
+    let tenant_id = user.tenant_id;
+    let user_id = user.user_id;
+    complete_mfa(&state, tenant_id, user_id, move |tx| {
+        Box::pin(async move { totp::verify_and_record(tx, tenant_id, user_id, &body.code).await })
+    })
+    .await
+}
+
+async fn webauthn_register_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Json(body): Json<WebauthnRegisterRequest>,
+) -> Result<StatusCode> {
+    user.require_mfa_complete()?;
+    let credential_id = hex::decode(&body.credential_id)
+        .map_err(|e| Error::BadRequest(format!("invalid credential_id: {e}")))?;
+    let public_key =
+        hex::decode(&body.public_key).map_err(|e| Error::BadRequest(format!("invalid public_key: {e}")))?;
+
+    state
+        .db
+        .with_tenant(user.tenant_id, |tx| {
+            Box::pin(async move {
+                webauthn::register(
+                    tx,
+                    user.tenant_id,
+                    user.user_id,
+                    &credential_id,
+                    &public_key,
+                    &body.algorithm,
+                )
+                .await
+            })
+        })
+        .await?;
+
+    Ok(StatusCode::CREATED)
+}
+
+/// Redeems a partial token for a full session by presenting a valid
+/// WebAuthn assertion. Same rationale as [`totp_verify_handler`] for not
+/// requiring a scope.
+async fn webauthn_verify_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Json(body): Json<WebauthnVerifyRequest>,
+) -> Result<Json<TokenPair>> {
+    let credential_id = hex::decode(&body.credential_id)
+        .map_err(|e| Error::BadRequest(format!("invalid credential_id: {e}")))?;
+    let client_data_json = hex::decode(&body.client_data_json)
+        .map_err(|e| Error::BadRequest(format!("invalid client_data_json: {e}")))?;
+    let authenticator_data = hex::decode(&body.authenticator_data)
+        .map_err(|e| Error::BadRequest(format!("invalid authenticator_data: {e}")))?;
+    let signature =
+        hex::decode(&body.signature).map_err(|e| Error::BadRequest(format!("invalid signature: {e}")))?;
+    let counter = body.counter;
+    let tenant_id = user.tenant_id;
+    let user_id = user.user_id;
+
+    complete_mfa(&state, tenant_id, user_id, move |tx| {
+        Box::pin(async move {
+            webauthn::verify_assertion(
+                tx,
+                user_id,
+                &credential_id,
+                &client_data_json,
+                &authenticator_data,
+                &signature,
+                counter,
+            )
+            .await
+        })
+    })
+    .await
+}
+
+/// Runs `verify` against the caller's enrolled factor and, only if it
+/// succeeds, mints the full token pair a completed login would have
+/// returned in the first place -- recomputing roles and scopes from the
+/// `users` row rather than trusting anything on the partial token, which
+/// never carried any.
+async fn complete_mfa<F>(state: &AppState, tenant_id: Uuid, user_id: Uuid, verify: F) -> Result<Json<TokenPair>>
+where
+    F: for<'a> FnOnce(
+            &'a mut sqlx::Transaction<'_, sqlx::Postgres>,
+        ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>>
+        + Send
+        + 'static,
+{
+    let tokens = state
+        .db
+        .with_tenant(tenant_id, |tx| {
+            Box::pin(async move {
+                verify(tx).await?;
+
+                let row = sqlx::query!("SELECT roles FROM users WHERE id = $1", user_id)
+                    .fetch_one(&mut **tx)
+                    .await?;
+                let scopes = scopes_for_roles(&row.roles);
+                let access_token = issue_token(user_id, tenant_id, row.roles, scopes)?;
+                let refresh_token = refresh::issue(tx, tenant_id, user_id).await?;
+                Ok(TokenPair {
+                    access_token,
+                    refresh_token,
+                })
+            })
+        })
+        .await?;
+
+    Ok(Json(tokens))
+}
@@ -0,0 +1,167 @@
+//! A simplified WebAuthn/FIDO2 relying-party: enough to register an
+//! authenticator's public key and verify its assertions, without parsing
+//! a full CBOR attestation object -- there's no real authenticator
+//! hardware anywhere in this backend-only environment to attest, so the
+//! bytes the client already has to extract for the signature check
+//! (credential id, public key, algorithm) are what gets stored directly.
+
+use ed25519_dalek::Verifier as _;
+use sha2::{Digest, Sha256};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Ed25519,
+    Es256,
+}
+
+impl Algorithm {
+    fn as_str(self) -> &'static str {
+        match self {
+            Algorithm::Ed25519 => "ed25519",
+            Algorithm::Es256 => "es256",
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "ed25519" => Ok(Algorithm::Ed25519),
+            "es256" => Ok(Algorithm::Es256),
+            other => Err(Error::BadRequest(format!(
+                "unsupported authenticator algorithm: {other}"
+            ))),
+        }
+    }
+}
+
+fn parse_public_key(algorithm: Algorithm, public_key: &[u8]) -> Result<()> {
+    match algorithm {
+        Algorithm::Ed25519 => {
+            let bytes: [u8; 32] = public_key
+                .try_into()
+                .map_err(|_| Error::BadRequest("ed25519 public key must be 32 bytes".to_string()))?;
+            ed25519_dalek::VerifyingKey::from_bytes(&bytes)
+                .map_err(|_| Error::BadRequest("invalid ed25519 public key".to_string()))?;
+        }
+        Algorithm::Es256 => {
+            p256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+                .map_err(|_| Error::BadRequest("invalid ES256 public key".to_string()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Registers a new authenticator for `user_id`, starting its signature
+/// counter at 0.
+pub async fn register(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    credential_id: &[u8],
+    public_key: &[u8],
+    algorithm: &str,
+) -> Result<()> {
+    let algorithm = Algorithm::parse(algorithm)?;
+    parse_public_key(algorithm, public_key)?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO webauthn_credentials
+            (credential_id, user_id, tenant_id, public_key, algorithm, signature_counter, created_at)
+        VALUES ($1, $2, $3, $4, $5, 0, NOW())
+        "#,
+        credential_id,
+        user_id,
+        tenant_id,
+        public_key,
+        algorithm.as_str(),
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+fn verify_signature(algorithm: Algorithm, public_key: &[u8], signed_data: &[u8], signature: &[u8]) -> Result<()> {
+    match algorithm {
+        Algorithm::Ed25519 => {
+            let key_bytes: [u8; 32] = public_key.try_into().map_err(|_| {
+                Error::Internal(anyhow::anyhow!("stored ed25519 public key has the wrong length"))
+            })?;
+            let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&key_bytes)
+                .map_err(|e| Error::Internal(anyhow::anyhow!("stored ed25519 public key is invalid: {e}")))?;
+            let sig_bytes: [u8; 64] = signature
+                .try_into()
+                .map_err(|_| Error::Unauthorized("malformed ed25519 signature".to_string()))?;
+            let signature = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+            verifying_key
+                .verify(signed_data, &signature)
+                .map_err(|_| Error::Unauthorized("assertion signature verification failed".to_string()))
+        }
+        Algorithm::Es256 => {
+            let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+                .map_err(|e| Error::Internal(anyhow::anyhow!("stored ES256 public key is invalid: {e}")))?;
+            let signature = p256::ecdsa::Signature::from_der(signature)
+                .or_else(|_| p256::ecdsa::Signature::from_slice(signature))
+                .map_err(|_| Error::Unauthorized("malformed ES256 signature".to_string()))?;
+            verifying_key
+                .verify(signed_data, &signature)
+                .map_err(|_| Error::Unauthorized("assertion signature verification failed".to_string()))
+        }
+    }
+}
+
+/// Verifies a WebAuthn assertion for `credential_id`: the signature must
+/// cover `authenticator_data || SHA-256(client_data_json)`, and
+/// `counter` -- the authenticator's signature counter on this assertion
+/// -- must be strictly greater than the last one it reported. A counter
+/// that doesn't advance means either a replayed assertion or a cloned
+/// authenticator; both are rejected the same way.
+pub async fn verify_assertion(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    credential_id: &[u8],
+    client_data_json: &[u8],
+    authenticator_data: &[u8],
+    signature: &[u8],
+    counter: i64,
+) -> Result<()> {
+    let row = sqlx::query!(
+        r#"
+        SELECT public_key, algorithm, signature_counter
+        FROM webauthn_credentials
+        WHERE credential_id = $1 AND user_id = $2
+        "#,
+        credential_id,
+        user_id,
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| Error::Unauthorized("unknown authenticator".to_string()))?;
+
+    if counter <= row.signature_counter {
+        return Err(Error::Unauthorized(
+            "authenticator signature counter did not advance; possible cloned authenticator".to_string(),
+        ));
+    }
+
+    let client_data_hash = Sha256::digest(client_data_json);
+    let mut signed_data = authenticator_data.to_vec();
+    signed_data.extend_from_slice(&client_data_hash);
+
+    let algorithm = Algorithm::parse(&row.algorithm)?;
+    verify_signature(algorithm, &row.public_key, &signed_data, signature)?;
+
+    sqlx::query!(
+        "UPDATE webauthn_credentials SET signature_counter = $1 WHERE credential_id = $2",
+        counter,
+        credential_id,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! Signed-JWT authentication.
+//!
+//! Every authenticated route takes an [`AuthenticatedUser`] extractor instead
+//! of trusting a tenant id from a path or body field: axum runs the
+//! extractor before the handler body, so a missing, forged, or expired token
+//! is rejected at the auth boundary and the handler never sees the request.
+//!
+//! Access tokens are short-lived and stateless; [`refresh`] and
+//! [`revocation`] cover the two things that can't be: staying signed in
+//! past an access token's `exp` without re-authenticating, and forcing one
+//! out before its `exp` arrives.
+
+mod delegation;
+mod jwt;
+pub mod mfa;
+pub mod refresh;
+pub mod revocation;
+mod routes;
+mod scopes;
+mod user;
+
+use axum::Router;
+use serde::Serialize;
+
+pub use jwt::{issue_partial_token, issue_token, verify_token, Claims};
+pub(crate) use scopes::scopes_for_roles;
+pub use user::AuthenticatedUser;
+
+/// An access token paired with the refresh token that can redeem the next
+/// one, returned by both sign-in and refresh rotation.
+#[derive(Debug, Serialize)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+/// Session routes ([`routes::router`]) merged with second-factor routes
+/// ([`mfa::router`]), so `main.rs` only has one thing to merge for
+/// everything this module owns.
+pub fn router() -> Router<crate::state::AppState> {
+    routes::router().merge(mfa::router())
+}
@@ -0,0 +1,135 @@
+//! Long-lived refresh tokens with family-based rotation and reuse
+//! detection.
+//!
+//! A refresh token is an opaque random value; only its SHA-256 hash is
+//! stored, so a leaked `refresh_tokens` row can't be replayed by itself.
+//! Every token issued at login belongs to a `family_id`: redeeming one
+//! marks it used and issues the next token in the same family. Presenting
+//! a token that was already used is reuse -- the rotation already
+//! happened, so this presentation can only be a stolen copy surfacing
+//! after the fact -- and revokes the *entire* family rather than just
+//! rejecting the one request, forcing the tenant's user to sign in again.
+
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+async fn insert(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    family_id: Uuid,
+) -> Result<String> {
+    let token = generate_token();
+    let expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO refresh_tokens (token_hash, family_id, user_id, tenant_id, expires_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, NOW())
+        "#,
+        hash_token(&token),
+        family_id,
+        user_id,
+        tenant_id,
+        expires_at,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(token)
+}
+
+/// Issues the first refresh token in a new family for `user_id`. Callers
+/// run this inside the same tenant-scoped transaction that mints the
+/// accompanying access token (see [`super::issue_token`]).
+pub async fn issue(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    user_id: Uuid,
+) -> Result<String> {
+    insert(tx, tenant_id, user_id, Uuid::new_v4()).await
+}
+
+/// Revokes every outstanding token in `family_id`.
+async fn revoke_family(tx: &mut Transaction<'_, Postgres>, family_id: Uuid) -> Result<()> {
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE family_id = $1 AND revoked_at IS NULL",
+        family_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Redeems `presented_token` for a new refresh token in the same family,
+/// returning the family's `user_id` and the replacement token.
+///
+/// A token that is unknown, revoked, or past its own `expires_at` is
+/// rejected outright. A token that has already been used is reuse: the
+/// legitimate rotation already consumed it, so this can only be a second
+/// party presenting a copy -- the whole family is revoked and `Err` is
+/// returned either way.
+pub async fn rotate(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    presented_token: &str,
+) -> Result<(Uuid, String)> {
+    let hash = hash_token(presented_token);
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(async move {
+            let row = sqlx::query!(
+                r#"
+                SELECT family_id, user_id, used_at, revoked_at, expires_at
+                FROM refresh_tokens
+                WHERE token_hash = $1
+                "#,
+                hash
+            )
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| Error::Unauthorized("unknown refresh token".to_string()))?;
+
+            if row.revoked_at.is_some() || row.expires_at < Utc::now() {
+                return Err(Error::Unauthorized(
+                    "refresh token is no longer valid".to_string(),
+                ));
+            }
+
+            if row.used_at.is_some() {
+                revoke_family(tx, row.family_id).await?;
+                return Err(Error::Unauthorized(
+                    "refresh token already used; session revoked".to_string(),
+                ));
+            }
+
+            sqlx::query!(
+                "UPDATE refresh_tokens SET used_at = NOW() WHERE token_hash = $1",
+                hash
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            let next = insert(tx, tenant_id, row.user_id, row.family_id).await?;
+            Ok((row.user_id, next))
+        })
+    })
+    .await
+}
@@ -0,0 +1,92 @@
+//! Routes for refreshing and revoking a session, as opposed to
+//! establishing one (see [`crate::identity::router`] for sign-in).
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use chrono::{TimeZone, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{delegation, issue_token, refresh, scopes_for_roles, AuthenticatedUser, TokenPair};
+use crate::error::Result;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct RefreshRequest {
+    pub tenant_id: Uuid,
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DelegateRequest {
+    pub scopes: Vec<String>,
+    /// Lifetime of the delegated token, in seconds. Capped well below a
+    /// normal session regardless of what's requested here.
+    #[serde(default)]
+    pub ttl_seconds: Option<i64>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct DelegateResponse {
+    pub access_token: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/auth/refresh", post(refresh_handler))
+        .route("/api/v1/auth/logout", post(logout_handler))
+        .route("/api/v1/auth/delegate", post(delegate_handler))
+}
+
+/// Redeems a refresh token for a new access/refresh pair. The new access
+/// token's roles and scopes are recomputed from the user's *current*
+/// `users` row rather than carried over from the old token, so a role
+/// change since login takes effect on the next refresh instead of only
+/// after the old access token expires.
+async fn refresh_handler(
+    State(state): State<AppState>,
+    Json(body): Json<RefreshRequest>,
+) -> Result<Json<TokenPair>> {
+    let (user_id, refresh_token) = refresh::rotate(&state.db, body.tenant_id, &body.refresh_token).await?;
+
+    let roles = state
+        .db
+        .with_tenant(body.tenant_id, |tx| {
+            Box::pin(async move {
+                let row = sqlx::query!("SELECT roles FROM users WHERE id = $1", user_id)
+                    .fetch_one(&mut **tx)
+                    .await?;
+                Ok(row.roles)
+            })
+        })
+        .await?;
+
+    let scopes = scopes_for_roles(&roles);
+    let access_token = issue_token(user_id, body.tenant_id, roles, scopes)?;
+
+    Ok(Json(TokenPair {
+        access_token,
+        refresh_token,
+    }))
+}
+
+/// Revokes the caller's own access token immediately rather than waiting
+/// for it to expire on its own.
+async fn logout_handler(State(state): State<AppState>, user: AuthenticatedUser) -> Result<StatusCode> {
+    let expires_at = Utc.timestamp_opt(user.exp, 0).single().unwrap_or_else(Utc::now);
+    state.revocation.revoke(user.jti, expires_at).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Mints a token scoped to a subset of the caller's own scopes, e.g. so an
+/// analyst can hand a collaborator a narrow `artifact:read:<id>` token
+/// without ever granting anything the analyst itself can't do.
+async fn delegate_handler(
+    user: AuthenticatedUser,
+    Json(body): Json<DelegateRequest>,
+) -> Result<Json<DelegateResponse>> {
+    let access_token = delegation::mint(&user, body.scopes, body.ttl_seconds)?;
+    Ok(Json(DelegateResponse { access_token }))
+}
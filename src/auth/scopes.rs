@@ -0,0 +1,41 @@
+/// The scopes granted to each Sentinel role an access token carries.
+///
+/// Shared by direct sign-in ([`crate::identity::login`]) and refresh-token
+/// rotation ([`super::refresh::rotate`]) so a token minted either way grants
+/// the same scopes for the same roles. A role with no entry grants no
+/// scopes rather than failing, so a tenant can map a group to a role before
+/// anything here knows what that role is for.
+pub(crate) fn scopes_for_roles(roles: &[String]) -> Vec<String> {
+    let mut scopes: Vec<String> = roles
+        .iter()
+        .flat_map(|role| match role.as_str() {
+            "viewer" => vec![
+                "artifact:read".to_string(),
+                "verdict:read".to_string(),
+                "siem:read".to_string(),
+                "webhook:read".to_string(),
+            ],
+            "analyst" => vec![
+                "artifact:read".to_string(),
+                "artifact:write".to_string(),
+                "verdict:read".to_string(),
+                "case:write".to_string(),
+            ],
+            "admin" => vec![
+                "artifact:read".to_string(),
+                "artifact:write".to_string(),
+                "verdict:read".to_string(),
+                "case:write".to_string(),
+                "endpoint:write".to_string(),
+                "siem:read".to_string(),
+                "siem:write".to_string(),
+                "webhook:read".to_string(),
+                "webhook:write".to_string(),
+            ],
+            _ => Vec::new(),
+        })
+        .collect();
+    scopes.sort();
+    scopes.dedup();
+    scopes
+}
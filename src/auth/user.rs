@@ -0,0 +1,115 @@
+use axum::async_trait;
+use axum::extract::{FromRef, FromRequestParts};
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use uuid::Uuid;
+
+use super::jwt::verify_token;
+use super::revocation::RevocationStore;
+use crate::error::Error;
+
+/// The caller of the current request, populated by extracting and verifying
+/// the `Authorization: Bearer` header and checking it against the
+/// [`RevocationStore`]. Handlers take this instead of a tenant id from the
+/// path or body, so a request can never act on a tenant it didn't
+/// authenticate as.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub user_id: Uuid,
+    pub tenant_id: Uuid,
+    pub roles: Vec<String>,
+    pub scopes: Vec<String>,
+    /// The access token's unique id, for revoking this one session
+    /// without affecting any other token the same user holds.
+    pub jti: Uuid,
+    /// The access token's expiry, as a unix timestamp. Revoking this
+    /// token only needs to last until this time passes on its own.
+    pub exp: i64,
+    /// False for the partial token a login hands back when the user has
+    /// a second factor enrolled but hasn't redeemed it yet. Checked by
+    /// [`require_scope`](Self::require_scope) so a partial token can
+    /// never reach a privileged route no matter what scopes it happens
+    /// to carry.
+    pub mfa_complete: bool,
+}
+
+impl AuthenticatedUser {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+
+    /// Rejects with [`Error::Forbidden`] unless a second factor has been
+    /// completed for this session.
+    pub fn require_mfa_complete(&self) -> crate::error::Result<()> {
+        if self.mfa_complete {
+            Ok(())
+        } else {
+            Err(Error::Forbidden(
+                "a second factor is required before this action".to_string(),
+            ))
+        }
+    }
+
+    /// Rejects with [`Error::Forbidden`] unless the token carries `scope`
+    /// and has completed any second factor enrolled for its user.
+    pub fn require_scope(&self, scope: &str) -> crate::error::Result<()> {
+        self.require_mfa_complete()?;
+        if self.has_scope(scope) {
+            Ok(())
+        } else {
+            Err(Error::Forbidden(format!("missing required scope: {scope}")))
+        }
+    }
+
+    /// Like [`require_scope`](Self::require_scope), but for an action
+    /// scoped to one specific resource: accepts either the resource-bound
+    /// scope (`action:resource_id`, as minted by [`super::delegation`]) or
+    /// the action's unscoped form held globally.
+    pub fn require_scope_for_resource(&self, action: &str, resource_id: Uuid) -> crate::error::Result<()> {
+        self.require_mfa_complete()?;
+        let scoped = format!("{action}:{resource_id}");
+        if self.scopes.iter().any(|s| s == action || *s == scoped) {
+            Ok(())
+        } else {
+            Err(Error::Forbidden(format!("missing required scope: {action}")))
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthenticatedUser
+where
+    RevocationStore: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| Error::Unauthorized("missing Authorization header".to_string()))?;
+
+        let token = header
+            .strip_prefix("Bearer ")
+            .ok_or_else(|| Error::Unauthorized("expected a Bearer token".to_string()))?;
+
+        let claims = verify_token(token)?;
+
+        let revocation = RevocationStore::from_ref(state);
+        if revocation.is_revoked(claims.jti).await? {
+            return Err(Error::Unauthorized("token has been revoked".to_string()));
+        }
+
+        Ok(AuthenticatedUser {
+            user_id: claims.sub,
+            tenant_id: claims.tenant_id,
+            roles: claims.roles,
+            scopes: claims.scopes,
+            jti: claims.jti,
+            exp: claims.exp,
+            mfa_complete: claims.mfa_complete,
+        })
+    }
+}
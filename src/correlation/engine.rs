@@ -0,0 +1,164 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::rules::Rule;
+use crate::telemetry::TelemetryEvent;
+
+/// An event retained in a key's ring buffer purely for the `window_secs`
+/// retention bound; matching itself is driven by [`RuleProgress`].
+struct ObservedEvent {
+    observed_at: Instant,
+}
+
+#[derive(Clone)]
+struct StageMatch {
+    event_id: Uuid,
+    tactic: String,
+}
+
+struct RuleProgress {
+    satisfied: Vec<Option<StageMatch>>,
+    last_match_at: Option<Instant>,
+}
+
+impl RuleProgress {
+    fn fresh(stage_count: usize) -> Self {
+        Self {
+            satisfied: vec![None; stage_count],
+            last_match_at: None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct KeyState {
+    events: VecDeque<ObservedEvent>,
+    progress: HashMap<String, RuleProgress>,
+}
+
+/// One contributing event in a fired [`AttackChainAlert`]'s
+/// `attack_chain`: its id and the MITRE tactic its stage represents.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainEvent {
+    pub event_id: Uuid,
+    pub tactic: String,
+}
+
+/// Emitted the moment every stage of a rule has matched within its
+/// window. `correlation_id` is fresh per alert, so re-running the same
+/// chain through the engine again (it can't, since firing resets
+/// progress) would never collide with a prior one.
+#[derive(Debug, Clone, Serialize)]
+pub struct AttackChainAlert {
+    pub correlation_id: Uuid,
+    pub tenant_id: Uuid,
+    pub endpoint_id: Uuid,
+    pub rule_name: String,
+    pub severity: i32,
+    pub attack_chain: Vec<ChainEvent>,
+}
+
+/// Stateful, per-`(tenant_id, endpoint_id)` multi-stage attack-chain
+/// correlation. See the module docs for the matching semantics.
+#[derive(Clone)]
+pub struct CorrelationEngine {
+    rules: Arc<Vec<Rule>>,
+    state: Arc<Mutex<HashMap<(Uuid, Uuid), KeyState>>>,
+}
+
+impl CorrelationEngine {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self {
+            rules: Arc::new(rules),
+            state: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Feeds one ingested telemetry event, tagged with `event_id`
+    /// (assigned by the caller at ingest time), through every configured
+    /// rule for its `(tenant_id, endpoint_id)`. Returns any attack
+    /// chains that completed as a result -- usually none, occasionally
+    /// one, never the same chain twice.
+    pub fn ingest(&self, event_id: Uuid, event: &TelemetryEvent) -> Vec<AttackChainAlert> {
+        if self.rules.is_empty() {
+            return Vec::new();
+        }
+
+        let now = Instant::now();
+        let key = (event.tenant_id, event.endpoint_id);
+        let max_window = self
+            .rules
+            .iter()
+            .map(|rule| rule.window())
+            .max()
+            .unwrap_or_default();
+
+        let mut state = self.state.lock().unwrap();
+        let key_state = state.entry(key).or_default();
+
+        key_state.events.push_back(ObservedEvent { observed_at: now });
+        while let Some(oldest) = key_state.events.front() {
+            if now.duration_since(oldest.observed_at) > max_window {
+                key_state.events.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let mut alerts = Vec::new();
+        for rule in self.rules.iter() {
+            let progress = key_state
+                .progress
+                .entry(rule.name.clone())
+                .or_insert_with(|| RuleProgress::fresh(rule.stages.len()));
+
+            if let Some(last_match_at) = progress.last_match_at {
+                if now.duration_since(last_match_at) > rule.inter_stage_timeout() {
+                    *progress = RuleProgress::fresh(rule.stages.len());
+                }
+            }
+
+            for (stage_index, stage) in rule.stages.iter().enumerate() {
+                if progress.satisfied[stage_index].is_none() && stage.matches(event) {
+                    progress.satisfied[stage_index] = Some(StageMatch {
+                        event_id,
+                        tactic: stage.tactic.clone(),
+                    });
+                    progress.last_match_at = Some(now);
+                    break;
+                }
+            }
+
+            if progress.satisfied.iter().all(Option::is_some) {
+                let attack_chain = progress
+                    .satisfied
+                    .iter_mut()
+                    .map(|slot| {
+                        let matched = slot.take().expect("all stages confirmed Some above");
+                        ChainEvent {
+                            event_id: matched.event_id,
+                            tactic: matched.tactic,
+                        }
+                    })
+                    .collect();
+
+                *progress = RuleProgress::fresh(rule.stages.len());
+
+                alerts.push(AttackChainAlert {
+                    correlation_id: Uuid::new_v4(),
+                    tenant_id: event.tenant_id,
+                    endpoint_id: event.endpoint_id,
+                    rule_name: rule.name.clone(),
+                    severity: rule.severity,
+                    attack_chain,
+                });
+            }
+        }
+
+        alerts
+    }
+}
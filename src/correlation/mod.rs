@@ -0,0 +1,27 @@
+//! Stateful attack-chain correlation.
+//!
+//! As telemetry events arrive through [`crate::telemetry`]'s ingest
+//! path, [`CorrelationEngine`] advances a per-`(tenant_id, endpoint_id)`,
+//! per-rule stage matcher and emits a single high-severity
+//! [`AttackChainAlert`] the first time every stage of a rule is
+//! satisfied within that rule's window. Rules (ordered MITRE-tactic
+//! stage sequences) are data -- see [`rules::load_rules`] -- rather than
+//! compiled in, so analysts can add sequences without a release;
+//! [`rules::default_rules`] ships the one
+//! `test_behavioral_correlation`-shaped chain (initial access ->
+//! execution -> privilege escalation -> lateral movement) for when no
+//! rules file is configured.
+//!
+//! A partial match that goes `inter_stage_timeout` without advancing is
+//! dropped rather than carried forward indefinitely. Any not-yet-matched
+//! stage (not just the next one in sequence) can be satisfied by an
+//! incoming event, so a chain still completes if its telemetry arrives
+//! out of order within the window. Firing a rule resets its progress for
+//! that key immediately, so the same matched events can't complete it a
+//! second time.
+
+mod engine;
+mod rules;
+
+pub use engine::{AttackChainAlert, ChainEvent, CorrelationEngine};
+pub use rules::{default_rules, load_rules, Rule, StagePredicate};
@@ -0,0 +1,118 @@
+//! Rule definitions for [`super::CorrelationEngine`]: an ordered
+//! sequence of stage predicates, each tagged with the MITRE ATT&CK
+//! tactic it represents.
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::telemetry::TelemetryEvent;
+
+/// One stage of a [`Rule`]. Every `Some` field must match for the stage
+/// to be satisfied by a given event; `None` fields aren't checked.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StagePredicate {
+    /// MITRE ATT&CK tactic this stage represents, e.g. `"initial-access"`.
+    pub tactic: String,
+    pub event_type: Option<String>,
+    pub process_name_contains: Option<String>,
+    #[serde(default)]
+    pub event_data_equals: Option<(String, serde_json::Value)>,
+}
+
+impl StagePredicate {
+    pub fn matches(&self, event: &TelemetryEvent) -> bool {
+        if let Some(expected) = &self.event_type {
+            if &event.event_type != expected {
+                return false;
+            }
+        }
+
+        if let Some(substr) = &self.process_name_contains {
+            let matches_process = event
+                .process_name
+                .as_deref()
+                .is_some_and(|name| name.contains(substr.as_str()));
+            if !matches_process {
+                return false;
+            }
+        }
+
+        if let Some((key, expected_value)) = &self.event_data_equals {
+            if event.event_data.get(key) != Some(expected_value) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// An ordered attack-chain rule. [`CorrelationEngine`](super::CorrelationEngine)
+/// fires it once every stage in `stages` has matched a distinct event
+/// within `window_secs`, provided no two consecutive stage matches are
+/// more than `inter_stage_timeout_secs` apart.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub name: String,
+    pub severity: i32,
+    pub window_secs: u64,
+    pub inter_stage_timeout_secs: u64,
+    pub stages: Vec<StagePredicate>,
+}
+
+impl Rule {
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window_secs)
+    }
+
+    pub fn inter_stage_timeout(&self) -> Duration {
+        Duration::from_secs(self.inter_stage_timeout_secs)
+    }
+}
+
+/// Loads rules from a JSON file of the shape in
+/// `config/correlation_rules.json`: a top-level array of [`Rule`].
+pub fn load_rules(path: &Path) -> anyhow::Result<Vec<Rule>> {
+    let contents = std::fs::read_to_string(path)?;
+    let rules = serde_json::from_str(&contents)?;
+    Ok(rules)
+}
+
+/// The built-in initial-access -> execution -> privilege-escalation ->
+/// lateral-movement chain, used when no rules file is configured.
+pub fn default_rules() -> Vec<Rule> {
+    vec![Rule {
+        name: "initial-access-to-lateral-movement".to_string(),
+        severity: 9,
+        window_secs: 900,
+        inter_stage_timeout_secs: 300,
+        stages: vec![
+            StagePredicate {
+                tactic: "initial-access".to_string(),
+                event_type: Some("phishing_attachment_opened".to_string()),
+                process_name_contains: None,
+                event_data_equals: None,
+            },
+            StagePredicate {
+                tactic: "execution".to_string(),
+                event_type: Some("process_created".to_string()),
+                process_name_contains: Some("powershell".to_string()),
+                event_data_equals: None,
+            },
+            StagePredicate {
+                tactic: "privilege-escalation".to_string(),
+                event_type: Some("token_privilege_adjusted".to_string()),
+                process_name_contains: None,
+                event_data_equals: None,
+            },
+            StagePredicate {
+                tactic: "lateral-movement".to_string(),
+                event_type: Some("remote_service_created".to_string()),
+                process_name_contains: None,
+                event_data_equals: None,
+            },
+        ],
+    }]
+}
@@ -0,0 +1,111 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use rand::RngCore;
+use uuid::Uuid;
+
+use super::kek::{DEK_LEN, NONCE_LEN};
+use crate::error::{Error, Result};
+
+/// A tenant's unwrapped 256-bit data-encryption-key. Only ever held
+/// in-memory for the duration of a request; never persisted or logged.
+pub struct TenantDek([u8; DEK_LEN]);
+
+/// An artifact blob sealed with AES-256-GCM under a tenant DEK.
+/// `key_version` records which of the tenant's DEKs sealed it, so old and
+/// new ciphertexts can coexist across a key rotation.
+#[derive(Debug, Clone)]
+pub struct SealedBlob {
+    pub key_version: i32,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+impl TenantDek {
+    pub(crate) fn new(bytes: [u8; DEK_LEN]) -> Self {
+        Self(bytes)
+    }
+
+    /// Exposes the raw key bytes. Only meant for code that has to hand the
+    /// DEK to another primitive operating on raw key material (e.g.
+    /// Umbral-encrypting it for a sharing grant, see `crate::sharing`);
+    /// never log or persist what this returns.
+    pub(crate) fn as_bytes(&self) -> &[u8; DEK_LEN] {
+        &self.0
+    }
+
+    /// Seals `plaintext`, binding `tenant_id` and `artifact_id` into the
+    /// GCM associated data so the ciphertext authenticates only for that
+    /// exact tenant/artifact pair.
+    pub fn seal(
+        &self,
+        tenant_id: Uuid,
+        artifact_id: Uuid,
+        key_version: i32,
+        plaintext: &[u8],
+    ) -> Result<SealedBlob> {
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0));
+
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let aad = associated_data(tenant_id, artifact_id);
+        let ciphertext = cipher
+            .encrypt(nonce, Payload { msg: plaintext, aad: &aad })
+            .map_err(|_| Error::Internal(anyhow::anyhow!("artifact encryption failed")))?;
+
+        Ok(SealedBlob {
+            key_version,
+            nonce: nonce_bytes.to_vec(),
+            ciphertext,
+        })
+    }
+
+    /// Opens `sealed`. Fails GCM authentication (and returns
+    /// [`Error::Unauthorized`]) if the blob was sealed under a different
+    /// DEK, or bound to a different tenant or artifact id.
+    pub fn open(&self, tenant_id: Uuid, artifact_id: Uuid, sealed: &SealedBlob) -> Result<Vec<u8>> {
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0));
+        let nonce = Nonce::from_slice(&sealed.nonce);
+        let aad = associated_data(tenant_id, artifact_id);
+
+        cipher
+            .decrypt(
+                nonce,
+                Payload {
+                    msg: &sealed.ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| Error::Unauthorized("artifact failed authentication under this key".to_string()))
+    }
+}
+
+fn associated_data(tenant_id: Uuid, artifact_id: Uuid) -> [u8; 32] {
+    let mut aad = [0u8; 32];
+    aad[..16].copy_from_slice(tenant_id.as_bytes());
+    aad[16..].copy_from_slice(artifact_id.as_bytes());
+    aad
+}
+
+/// Opens `sealed` with the DEK for its own `key_version`; if that isn't
+/// the tenant's current key version, also returns the plaintext re-sealed
+/// under `current_dek` so the caller can persist the upgrade. This is how
+/// rotation gets applied lazily: an artifact is migrated onto the
+/// tenant's newest key the next time anything reads it, rather than all
+/// at once at rotation time.
+pub fn open_and_maybe_reseal(
+    sealing_dek: &TenantDek,
+    current_dek: &TenantDek,
+    current_version: i32,
+    tenant_id: Uuid,
+    artifact_id: Uuid,
+    sealed: &SealedBlob,
+) -> Result<(Vec<u8>, Option<SealedBlob>)> {
+    let plaintext = sealing_dek.open(tenant_id, artifact_id, sealed)?;
+    if sealed.key_version == current_version {
+        return Ok((plaintext, None));
+    }
+    let resealed = current_dek.seal(tenant_id, artifact_id, current_version, &plaintext)?;
+    Ok((plaintext, Some(resealed)))
+}
@@ -0,0 +1,90 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use rand::RngCore;
+
+use crate::error::{Error, Result};
+use crate::crypto::envelope::TenantDek;
+
+pub const DEK_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
+
+/// A tenant DEK, encrypted (wrapped) under the KEK. This is the only form
+/// of the DEK that is ever persisted.
+#[derive(Debug, Clone)]
+pub struct WrappedDek {
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+/// The master key-encryption-key for this deployment.
+#[derive(Clone)]
+pub struct Kek {
+    cipher: Aes256Gcm,
+}
+
+impl Kek {
+    pub fn new(key_bytes: &[u8; DEK_LEN]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key_bytes)),
+        }
+    }
+
+    /// Loads the KEK from `SENTINEL_MASTER_KEY` (32 raw bytes, hex-encoded).
+    /// A real deployment sources this from a KMS; the env var is the
+    /// integration point until one is wired in.
+    pub fn from_env() -> Result<Self> {
+        let hex_key = std::env::var("SENTINEL_MASTER_KEY")
+            .map_err(|_| Error::Internal(anyhow::anyhow!("SENTINEL_MASTER_KEY is not set")))?;
+        let bytes = hex::decode(hex_key)
+            .map_err(|e| Error::Internal(anyhow::anyhow!("SENTINEL_MASTER_KEY is not valid hex: {e}")))?;
+        let key: [u8; DEK_LEN] = bytes
+            .try_into()
+            .map_err(|_| Error::Internal(anyhow::anyhow!("SENTINEL_MASTER_KEY must be {DEK_LEN} bytes")))?;
+        Ok(Self::new(&key))
+    }
+
+    /// Generates a fresh tenant DEK and returns it both in the clear (to
+    /// seal artifacts with immediately) and wrapped under this KEK (to
+    /// persist).
+    pub fn generate_and_wrap_dek(&self) -> Result<(TenantDek, WrappedDek)> {
+        let mut dek_bytes = [0u8; DEK_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut dek_bytes);
+
+        let wrapped = self.wrap(&dek_bytes)?;
+        Ok((TenantDek::new(dek_bytes), wrapped))
+    }
+
+    pub fn wrap(&self, dek: &[u8; DEK_LEN]) -> Result<WrappedDek> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        rand::rngs::OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(nonce, dek.as_slice())
+            .map_err(|_| Error::Internal(anyhow::anyhow!("failed to wrap data key")))?;
+
+        Ok(WrappedDek {
+            nonce: nonce_bytes.to_vec(),
+            ciphertext,
+        })
+    }
+
+    pub fn unwrap(&self, wrapped: &WrappedDek) -> Result<TenantDek> {
+        let nonce = Nonce::from_slice(&wrapped.nonce);
+        let payload = Payload {
+            msg: &wrapped.ciphertext,
+            aad: &[],
+        };
+
+        let dek_bytes = self
+            .cipher
+            .decrypt(nonce, payload)
+            .map_err(|_| Error::Internal(anyhow::anyhow!("failed to unwrap data key")))?;
+        let dek_bytes: [u8; DEK_LEN] = dek_bytes
+            .try_into()
+            .map_err(|_| Error::Internal(anyhow::anyhow!("unwrapped data key has the wrong length")))?;
+
+        Ok(TenantDek::new(dek_bytes))
+    }
+}
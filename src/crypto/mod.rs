@@ -0,0 +1,17 @@
+//! Envelope encryption for tenant data.
+//!
+//! One master key-encryption-key (KEK, see [`Kek`]) lives in a configured
+//! KMS/secret and never touches disk. Each tenant gets its own 256-bit
+//! data-encryption-key (DEK), generated once and stored only in its
+//! KEK-wrapped form in `tenant_data_keys`; artifact blobs are sealed under
+//! the tenant's DEK with AES-256-GCM (see [`TenantDek`]), binding the
+//! tenant and artifact ids into the GCM associated data so a ciphertext can
+//! never be replayed onto another tenant or another artifact's row.
+
+mod envelope;
+mod kek;
+mod rotation;
+
+pub use envelope::{open_and_maybe_reseal, SealedBlob, TenantDek};
+pub use kek::{Kek, WrappedDek, DEK_LEN};
+pub use rotation::{current_tenant_dek, rotate_tenant_key};
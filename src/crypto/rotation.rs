@@ -0,0 +1,138 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use super::envelope::TenantDek;
+use super::kek::{Kek, WrappedDek};
+use crate::error::Result;
+
+/// Generates a new DEK for `tenant_id`, wraps it under the KEK, and makes
+/// it the tenant's active key version. Existing artifacts stay sealed
+/// under their old key version until something reads them (see
+/// [`super::open_and_maybe_reseal`]) — rotation itself never touches
+/// artifact rows, so it's cheap and safe to run online.
+pub async fn rotate_tenant_key(pool: &PgPool, kek: &Kek, tenant_id: Uuid) -> Result<i32> {
+    let (_dek, wrapped) = kek.generate_and_wrap_dek()?;
+
+    let mut tx = pool.begin().await?;
+
+    let next_version: i32 = sqlx::query_scalar!(
+        "SELECT current_key_version + 1 FROM tenants WHERE id = $1 FOR UPDATE",
+        tenant_id
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .unwrap_or(1);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO tenant_data_keys (tenant_id, key_version, wrapped_dek, wrap_nonce)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        tenant_id,
+        next_version,
+        wrapped.ciphertext,
+        wrapped.nonce
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE tenants SET current_key_version = $1 WHERE id = $2",
+        next_version,
+        tenant_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(next_version)
+}
+
+/// Loads and unwraps `tenant_id`'s current DEK within an already
+/// tenant-scoped transaction. Used by anything that needs to seal or open
+/// artifacts with the tenant's live key, e.g. `crate::sharing`.
+///
+/// Nothing provisions a tenant's first DEK up front -- a tenant row starts
+/// life at `current_key_version = 1` with no matching `tenant_data_keys`
+/// row -- so this lazily generates and wraps one the first time it's
+/// needed, rather than requiring every tenant-creation path to remember to
+/// call [`rotate_tenant_key`] itself.
+pub async fn current_tenant_dek(
+    tx: &mut Transaction<'_, Postgres>,
+    kek: &Kek,
+    tenant_id: Uuid,
+) -> Result<(TenantDek, i32)> {
+    let row = match fetch_current_key(tx, tenant_id).await? {
+        Some(row) => row,
+        None => {
+            initialize_tenant_key(tx, kek, tenant_id).await?;
+            fetch_current_key(tx, tenant_id)
+                .await?
+                .expect("a key was just initialized for this tenant")
+        }
+    };
+
+    let wrapped = WrappedDek {
+        nonce: row.wrap_nonce,
+        ciphertext: row.wrapped_dek,
+    };
+    let dek = kek.unwrap(&wrapped)?;
+    Ok((dek, row.key_version))
+}
+
+struct CurrentKeyRow {
+    key_version: i32,
+    wrapped_dek: Vec<u8>,
+    wrap_nonce: Vec<u8>,
+}
+
+async fn fetch_current_key(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+) -> Result<Option<CurrentKeyRow>> {
+    let row = sqlx::query_as!(
+        CurrentKeyRow,
+        r#"
+        SELECT t.current_key_version AS "key_version!", dk.wrapped_dek, dk.wrap_nonce
+        FROM tenants t
+        JOIN tenant_data_keys dk
+          ON dk.tenant_id = t.id AND dk.key_version = t.current_key_version
+        WHERE t.id = $1
+        "#,
+        tenant_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(row)
+}
+
+/// Generates and wraps a tenant's first DEK at `current_key_version`
+/// (normally 1, since this only runs when no `tenant_data_keys` row
+/// exists yet). `ON CONFLICT DO NOTHING` covers two concurrent first
+/// reads racing to initialize the same tenant -- whichever loses just
+/// re-fetches the winner's row.
+async fn initialize_tenant_key(
+    tx: &mut Transaction<'_, Postgres>,
+    kek: &Kek,
+    tenant_id: Uuid,
+) -> Result<()> {
+    let (_dek, wrapped) = kek.generate_and_wrap_dek()?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO tenant_data_keys (tenant_id, key_version, wrapped_dek, wrap_nonce)
+        SELECT t.id, t.current_key_version, $2, $3
+        FROM tenants t
+        WHERE t.id = $1
+        ON CONFLICT (tenant_id, key_version) DO NOTHING
+        "#,
+        tenant_id,
+        wrapped.ciphertext,
+        wrapped.nonce
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
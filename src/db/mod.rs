@@ -0,0 +1,3 @@
+pub mod tenancy;
+
+pub use tenancy::TenantScopedPool;
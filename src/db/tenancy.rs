@@ -0,0 +1,67 @@
+//! Database-enforced tenant isolation.
+//!
+//! Handlers are easy to get wrong: one query that forgets `WHERE tenant_id = $1`
+//! silently leaks another tenant's rows. [`TenantScopedPool`] makes that class of
+//! bug structurally impossible by never handing out a bare connection — every
+//! query runs inside a transaction that has already told Postgres which tenant
+//! it is allowed to see via `SET LOCAL app.current_tenant`, and Row Level
+//! Security policies (see `migrations/0001_enable_row_level_security.sql`) do
+//! the actual filtering at the storage layer.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// A `PgPool` that can only be queried through [`TenantScopedPool::with_tenant`].
+///
+/// This intentionally does not expose the inner pool: any code that needs
+/// database access must go through a tenant-bound transaction so RLS policies
+/// are always in effect.
+#[derive(Clone)]
+pub struct TenantScopedPool {
+    pool: PgPool,
+}
+
+impl TenantScopedPool {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Runs `f` inside a transaction scoped to `tenant_id`.
+    ///
+    /// `SET LOCAL app.current_tenant` is issued first so every statement `f`
+    /// executes is subject to the `USING (tenant_id = current_setting(...))`
+    /// RLS policies, even if `f` forgets to filter by tenant itself. The
+    /// setting is transaction-local and is discarded on commit/rollback.
+    pub async fn with_tenant<T, F>(&self, tenant_id: Uuid, f: F) -> Result<T>
+    where
+        F: for<'c> FnOnce(
+            &'c mut Transaction<'_, Postgres>,
+        ) -> Pin<Box<dyn Future<Output = Result<T>> + Send + 'c>>,
+    {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("SELECT set_config('app.current_tenant', $1, true)")
+            .bind(tenant_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+
+        let result = f(&mut tx).await?;
+
+        tx.commit().await?;
+        Ok(result)
+    }
+}
+
+/// Convenience macro-free helper for the common "boxed async closure" shape
+/// required by [`TenantScopedPool::with_tenant`].
+#[macro_export]
+macro_rules! tenant_scope {
+    (|$tx:ident| $body:expr) => {
+        |$tx: &mut sqlx::Transaction<'_, sqlx::Postgres>| Box::pin(async move { $body })
+    };
+}
@@ -0,0 +1,38 @@
+use sqlx::{Postgres, Transaction};
+
+use crate::error::Result;
+
+/// Credentials presented at sign-in. The same shape is checked against a
+/// local password hash by [`super::SqlIdentityBackend`] or used to bind
+/// against an external directory by [`super::LdapIdentityBackend`].
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub username: String,
+    pub secret: String,
+}
+
+/// What a backend found out about the caller once `authenticate` succeeds.
+/// `groups` are backend-native names (an LDAP `memberOf` DN, a row in
+/// `local_credentials.groups`, ...); translating them to Sentinel roles is
+/// `provision::login`'s job, not the backend's, so the same per-tenant
+/// mapping applies no matter which backend produced the identity.
+#[derive(Debug, Clone)]
+pub struct DirectoryIdentity {
+    pub external_id: String,
+    pub email: String,
+    pub groups: Vec<String>,
+}
+
+/// A source of truth for "who is this, and what groups do they belong
+/// to". `tx` is already scoped to the tenant being signed into (see
+/// [`crate::db::TenantScopedPool::with_tenant`]): a backend that queries
+/// Postgres only ever sees that tenant's rows, and one that doesn't need
+/// Postgres at all just ignores the argument.
+#[axum::async_trait]
+pub trait IdentityBackend: Send + Sync {
+    async fn authenticate(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        credentials: &Credentials,
+    ) -> Result<DirectoryIdentity>;
+}
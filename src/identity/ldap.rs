@@ -0,0 +1,70 @@
+use ldap3::{LdapConnAsync, Scope, SearchEntry};
+use sqlx::{Postgres, Transaction};
+
+use super::backend::{Credentials, DirectoryIdentity, IdentityBackend};
+use crate::error::{Error, Result};
+
+/// Federates sign-in to an external LDAP directory: binds as the caller,
+/// which proves their password to the directory itself rather than to
+/// Sentinel, then reads back their `mail` and `memberOf` attributes.
+/// `bind_dn_template` has `{username}` substituted in, e.g.
+/// `"uid={username},ou=people,dc=example,dc=com"`.
+pub struct LdapIdentityBackend {
+    url: String,
+    bind_dn_template: String,
+}
+
+impl LdapIdentityBackend {
+    pub fn new(url: String, bind_dn_template: String) -> Self {
+        Self { url, bind_dn_template }
+    }
+}
+
+#[axum::async_trait]
+impl IdentityBackend for LdapIdentityBackend {
+    async fn authenticate(
+        &self,
+        _tx: &mut Transaction<'_, Postgres>,
+        credentials: &Credentials,
+    ) -> Result<DirectoryIdentity> {
+        let bind_dn = self.bind_dn_template.replace("{username}", &credentials.username);
+
+        let (conn, mut ldap) = LdapConnAsync::new(&self.url)
+            .await
+            .map_err(|e| Error::Internal(anyhow::anyhow!("could not reach directory: {e}")))?;
+        ldap3::drive!(conn);
+
+        ldap.simple_bind(&bind_dn, &credentials.secret)
+            .await
+            .and_then(|res| res.success())
+            .map_err(|_| Error::Unauthorized("invalid directory credentials".to_string()))?;
+
+        let (entries, _) = ldap
+            .search(&bind_dn, Scope::Base, "(objectClass=*)", vec!["mail", "memberOf"])
+            .await
+            .and_then(|res| res.success())
+            .map_err(|e| Error::Internal(anyhow::anyhow!("directory search failed: {e}")))?;
+
+        let _ = ldap.unbind().await;
+
+        let entry = entries
+            .into_iter()
+            .next()
+            .map(SearchEntry::construct)
+            .ok_or_else(|| Error::Unauthorized("directory entry not found after bind".to_string()))?;
+
+        let email = entry
+            .attrs
+            .get("mail")
+            .and_then(|values| values.first())
+            .cloned()
+            .unwrap_or_else(|| credentials.username.clone());
+        let groups = entry.attrs.get("memberOf").cloned().unwrap_or_default();
+
+        Ok(DirectoryIdentity {
+            external_id: bind_dn,
+            email,
+            groups,
+        })
+    }
+}
@@ -0,0 +1,27 @@
+//! Pluggable external identity for tenant user provisioning.
+//!
+//! Seeding a `users` row by hand assumes Sentinel is the source of truth
+//! for who belongs to a tenant. Real tenants want their own directory to
+//! govern that instead: [`IdentityBackend`] is the extension point -- the
+//! built-in [`SqlIdentityBackend`] authenticates against credentials
+//! stored directly in Postgres, and [`LdapIdentityBackend`] federates to
+//! an external directory (an OIDC backend would implement the same trait).
+//! [`login`] is the only way in: it picks the backend configured for the
+//! tenant being signed into, authenticates, translates the directory's
+//! groups to Sentinel roles using that tenant's own mapping, and
+//! just-in-time provisions (or updates) the resulting `users` row -- all
+//! inside a single transaction scoped to that tenant_id, so a login can
+//! never provision a user into a tenant other than the one it
+//! authenticated against.
+
+mod backend;
+mod ldap;
+mod provision;
+mod routes;
+mod sql;
+
+pub use backend::{Credentials, DirectoryIdentity, IdentityBackend};
+pub use ldap::LdapIdentityBackend;
+pub use provision::{login, LoginOutcome};
+pub use routes::router;
+pub use sql::{hash_password, SqlIdentityBackend};
@@ -0,0 +1,148 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use super::backend::{Credentials, DirectoryIdentity, IdentityBackend};
+use super::ldap::LdapIdentityBackend;
+use super::sql::SqlIdentityBackend;
+use crate::auth::{issue_partial_token, issue_token, mfa, refresh, scopes_for_roles, TokenPair};
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+
+/// What a completed sign-in produces: either a full session, or -- if the
+/// user has a second factor enrolled -- a partial token that must be
+/// redeemed at one of [`crate::auth::mfa`]'s verify endpoints before it
+/// becomes one.
+pub enum LoginOutcome {
+    Complete(TokenPair),
+    MfaRequired { partial_token: String },
+}
+
+async fn load_backend(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+) -> Result<Box<dyn IdentityBackend>> {
+    let config = sqlx::query!(
+        r#"
+        SELECT backend, ldap_url, ldap_bind_dn_template
+        FROM tenant_identity_configs
+        WHERE tenant_id = $1
+        "#,
+        tenant_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let backend: Box<dyn IdentityBackend> = match config {
+        Some(row) if row.backend == "ldap" => {
+            let url = row.ldap_url.ok_or_else(|| {
+                Error::Internal(anyhow::anyhow!(
+                    "tenant is configured for the ldap backend but has no ldap_url"
+                ))
+            })?;
+            let bind_dn_template = row.ldap_bind_dn_template.ok_or_else(|| {
+                Error::Internal(anyhow::anyhow!(
+                    "tenant is configured for the ldap backend but has no ldap_bind_dn_template"
+                ))
+            })?;
+            Box::new(LdapIdentityBackend::new(url, bind_dn_template))
+        }
+        _ => Box::new(SqlIdentityBackend),
+    };
+    Ok(backend)
+}
+
+/// Translates directory group names to Sentinel roles via this tenant's
+/// own `tenant_role_mappings` -- a group unmapped for this tenant is
+/// silently dropped rather than granting a role, so a name that happens to
+/// match another tenant's mapping can't grant anything here.
+async fn map_groups_to_roles(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    groups: &[String],
+) -> Result<Vec<String>> {
+    if groups.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut roles = sqlx::query_scalar!(
+        r#"
+        SELECT role FROM tenant_role_mappings
+        WHERE tenant_id = $1 AND directory_group = ANY($2)
+        "#,
+        tenant_id,
+        groups
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    roles.sort();
+    roles.dedup();
+    Ok(roles)
+}
+
+/// Inserts or updates the `users` row for `identity`, keyed on
+/// `(tenant_id, email)` so repeat sign-ins update the existing row's roles
+/// instead of minting a new user every time.
+async fn provision_user(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    identity: &DirectoryIdentity,
+    roles: &[String],
+) -> Result<Uuid> {
+    let user_id = Uuid::new_v4();
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO users (id, tenant_id, email, roles, external_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, NOW())
+        ON CONFLICT (tenant_id, email) DO UPDATE SET
+            roles = EXCLUDED.roles,
+            external_id = EXCLUDED.external_id
+        RETURNING id
+        "#,
+        user_id,
+        tenant_id,
+        identity.email,
+        roles,
+        identity.external_id,
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(row.id)
+}
+
+/// Authenticates `credentials` against whatever identity backend
+/// `tenant_id` is configured for and just-in-time provisions (or updates)
+/// the matching `users` row. A user with no second factor enrolled gets a
+/// full session back; one with a factor enrolled gets a partial token
+/// that must be redeemed at one of [`crate::auth::mfa`]'s verify
+/// endpoints before it can do anything privileged.
+///
+/// Backend selection, group mapping, provisioning, the MFA-enrollment
+/// check, and issuing the first refresh token of a new family all run
+/// inside a single transaction scoped to `tenant_id` (see
+/// [`TenantScopedPool::with_tenant`]), so a credential that authenticates
+/// against one tenant's directory can never end up minting -- or
+/// updating -- a user row in a different tenant.
+pub async fn login(db: &TenantScopedPool, tenant_id: Uuid, credentials: Credentials) -> Result<LoginOutcome> {
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(async move {
+            let backend = load_backend(tx, tenant_id).await?;
+            let identity = backend.authenticate(tx, &credentials).await?;
+            let roles = map_groups_to_roles(tx, tenant_id, &identity.groups).await?;
+            let user_id = provision_user(tx, tenant_id, &identity, &roles).await?;
+
+            if mfa::is_enrolled(tx, user_id).await? {
+                let partial_token = issue_partial_token(user_id, tenant_id)?;
+                return Ok(LoginOutcome::MfaRequired { partial_token });
+            }
+
+            let scopes = scopes_for_roles(&roles);
+            let access_token = issue_token(user_id, tenant_id, roles, scopes)?;
+            let refresh_token = refresh::issue(tx, tenant_id, user_id).await?;
+            Ok(LoginOutcome::Complete(TokenPair {
+                access_token,
+                refresh_token,
+            }))
+        })
+    })
+    .await
+}
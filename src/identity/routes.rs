@@ -0,0 +1,67 @@
+use axum::extract::{Path, State};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::backend::Credentials;
+use super::provision::{login, LoginOutcome};
+use crate::error::Result;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+/// Either a full session (`token`/`refresh_token`) or, if the user has a
+/// second factor enrolled, `mfa_required` with a `partial_token` to
+/// redeem at one of `/api/v1/auth/mfa/*`'s verify endpoints.
+#[derive(Debug, Serialize)]
+pub struct LoginResponse {
+    pub mfa_required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_token: Option<String>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/v1/tenants/:tenant_id/login", post(login_handler))
+}
+
+async fn login_handler(
+    Path(tenant_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Json(body): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>> {
+    let outcome = login(
+        &state.db,
+        tenant_id,
+        Credentials {
+            username: body.username,
+            secret: body.password,
+        },
+    )
+    .await?;
+
+    let response = match outcome {
+        LoginOutcome::Complete(tokens) => LoginResponse {
+            mfa_required: false,
+            token: Some(tokens.access_token),
+            refresh_token: Some(tokens.refresh_token),
+            partial_token: None,
+        },
+        LoginOutcome::MfaRequired { partial_token } => LoginResponse {
+            mfa_required: true,
+            token: None,
+            refresh_token: None,
+            partial_token: Some(partial_token),
+        },
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,55 @@
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::rngs::OsRng;
+use sqlx::{Postgres, Transaction};
+
+use super::backend::{Credentials, DirectoryIdentity, IdentityBackend};
+use crate::error::{Error, Result};
+
+/// Default backend: authenticates against password hashes stored directly
+/// in `local_credentials`, for tenants that don't federate to an external
+/// directory.
+#[derive(Debug, Default)]
+pub struct SqlIdentityBackend;
+
+#[axum::async_trait]
+impl IdentityBackend for SqlIdentityBackend {
+    async fn authenticate(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        credentials: &Credentials,
+    ) -> Result<DirectoryIdentity> {
+        let row = sqlx::query!(
+            r#"
+            SELECT username, password_hash, email, groups
+            FROM local_credentials
+            WHERE username = $1
+            "#,
+            credentials.username
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| Error::Unauthorized("invalid credentials".to_string()))?;
+
+        let parsed_hash = PasswordHash::new(&row.password_hash)
+            .map_err(|e| Error::Internal(anyhow::anyhow!("stored password hash is malformed: {e}")))?;
+        Argon2::default()
+            .verify_password(credentials.secret.as_bytes(), &parsed_hash)
+            .map_err(|_| Error::Unauthorized("invalid credentials".to_string()))?;
+
+        Ok(DirectoryIdentity {
+            external_id: row.username,
+            email: row.email,
+            groups: row.groups,
+        })
+    }
+}
+
+/// Hashes `password` for storage in `local_credentials.password_hash`.
+pub fn hash_password(password: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| Error::Internal(anyhow::anyhow!("failed to hash password: {e}")))
+}
@@ -0,0 +1,19 @@
+pub mod artifacts;
+pub mod auth;
+pub mod correlation;
+pub mod crypto;
+pub mod db;
+pub mod error;
+pub mod identity;
+pub mod metrics;
+pub mod policy;
+pub mod ratelimit;
+pub mod sandbox;
+pub mod sharing;
+pub mod siem;
+pub mod state;
+pub mod telemetry;
+pub mod verdicts;
+pub mod webhooks;
+
+pub use error::{Error, Result};
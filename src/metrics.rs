@@ -0,0 +1,114 @@
+//! Prometheus metrics for the parts of the pipeline the integration
+//! tests exercise directly: telemetry ingestion throughput (see
+//! [`crate::telemetry`]) and verdict delivery latency, the one real
+//! analog in this tree for the alert-delivery SLA
+//! `test_real_time_alert_delivery` asserts -- there's no detection
+//! engine here yet to emit a `severity`/`detection_rule`-labeled alerts
+//! counter against, so that piece is left for whenever that subsystem
+//! lands.
+
+use std::sync::OnceLock;
+
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use prometheus::{Encoder, Histogram, HistogramOpts, IntCounterVec, Opts, Registry, TextEncoder};
+
+use crate::state::AppState;
+
+/// The outcome a telemetry event reached by the time ingestion finished
+/// with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryOutcome {
+    Ingested,
+    Stored,
+    Dropped,
+}
+
+impl TelemetryOutcome {
+    fn label(self) -> &'static str {
+        match self {
+            TelemetryOutcome::Ingested => "ingested",
+            TelemetryOutcome::Stored => "stored",
+            TelemetryOutcome::Dropped => "dropped",
+        }
+    }
+}
+
+pub struct Metrics {
+    registry: Registry,
+    telemetry_events: IntCounterVec,
+    verdict_delivery_latency: Histogram,
+}
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let telemetry_events = IntCounterVec::new(
+            Opts::new(
+                "sentinel_telemetry_events_total",
+                "Telemetry events processed by ingestion, labeled by tenant_id, event_type, and outcome (ingested/stored/dropped).",
+            ),
+            &["tenant_id", "event_type", "outcome"],
+        )
+        .expect("static metric options are valid");
+        registry
+            .register(Box::new(telemetry_events.clone()))
+            .expect("metric name is unique");
+
+        let verdict_delivery_latency = Histogram::with_opts(HistogramOpts::new(
+            "sentinel_verdict_delivery_latency_seconds",
+            "Time from a verdict being published to it being pushed to a live WebSocket/SSE subscriber.",
+        ))
+        .expect("static metric options are valid");
+        registry
+            .register(Box::new(verdict_delivery_latency.clone()))
+            .expect("metric name is unique");
+
+        Self {
+            registry,
+            telemetry_events,
+            verdict_delivery_latency,
+        }
+    }
+
+    /// The process-wide registry. Metrics are cheap, label-bounded
+    /// counters and histograms shared across every tenant, so one
+    /// lazily-initialized instance per process is simpler than threading
+    /// a handle through `AppState` for something that never needs
+    /// per-tenant isolation.
+    pub fn global() -> &'static Metrics {
+        METRICS.get_or_init(Metrics::new)
+    }
+
+    pub fn record_telemetry_event(&self, tenant_id: uuid::Uuid, event_type: &str, outcome: TelemetryOutcome) {
+        self.telemetry_events
+            .with_label_values(&[&tenant_id.to_string(), event_type, outcome.label()])
+            .inc();
+    }
+
+    pub fn observe_verdict_delivery_latency(&self, latency_secs: f64) {
+        self.verdict_delivery_latency.observe(latency_secs);
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/metrics", get(metrics_handler))
+}
+
+async fn metrics_handler() -> impl IntoResponse {
+    let metrics = Metrics::global();
+    let encoder = TextEncoder::new();
+    let families = metrics.registry.gather();
+
+    let mut buffer = Vec::new();
+    encoder
+        .encode(&families, &mut buffer)
+        .expect("prometheus text encoding never fails");
+
+    ([(CONTENT_TYPE, encoder.format_type().to_string())], buffer)
+}
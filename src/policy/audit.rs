@@ -0,0 +1,102 @@
+//! Persists every [`super::DeliveryDecision`] a policy engine makes, so a
+//! `Suppress`/`Defer` outcome is visible to an operator even though nothing
+//! downstream ever sees the verdict it was made about.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::Result;
+
+use super::{DeliveryDecision, Integration, VerdictContext};
+
+#[derive(Debug, Serialize)]
+pub struct PolicyDecisionRecord {
+    pub id: Uuid,
+    pub integration: String,
+    pub integration_id: Uuid,
+    pub artifact_id: Uuid,
+    pub decision: String,
+    pub reason: Option<String>,
+    pub decided_at: DateTime<Utc>,
+}
+
+pub(super) async fn record(db: &TenantScopedPool, ctx: &VerdictContext, decision: &DeliveryDecision) -> Result<()> {
+    let id = Uuid::new_v4();
+    let integration = ctx.integration.as_str();
+    let integration_id = ctx.integration_id;
+    let artifact_id = ctx.artifact_id;
+    let label = decision.label();
+    let reason = decision.reason().map(str::to_string);
+
+    db.with_tenant(ctx.tenant_id, |tx| {
+        Box::pin(insert(tx, id, ctx.tenant_id, integration, integration_id, artifact_id, label, reason))
+    })
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn insert(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    tenant_id: Uuid,
+    integration: &str,
+    integration_id: Uuid,
+    artifact_id: Uuid,
+    decision: &str,
+    reason: Option<String>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO policy_decisions (id, tenant_id, integration, integration_id, artifact_id, decision, reason)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        id,
+        tenant_id,
+        integration,
+        integration_id,
+        artifact_id,
+        decision,
+        reason,
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// The most recent decisions for a single integration, newest first --
+/// what a status page would show to explain a gap in delivered verdicts.
+pub async fn list_decisions(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    integration: Integration,
+    integration_id: Uuid,
+) -> Result<Vec<PolicyDecisionRecord>> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_recent(tx, integration.as_str(), integration_id)))
+        .await
+}
+
+async fn fetch_recent(
+    tx: &mut Transaction<'_, Postgres>,
+    integration: &str,
+    integration_id: Uuid,
+) -> Result<Vec<PolicyDecisionRecord>> {
+    let rows = sqlx::query_as!(
+        PolicyDecisionRecord,
+        r#"
+        SELECT id, integration, integration_id, artifact_id, decision, reason, decided_at
+        FROM policy_decisions
+        WHERE integration = $1 AND integration_id = $2
+        ORDER BY decided_at DESC
+        LIMIT 100
+        "#,
+        integration,
+        integration_id,
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows)
+}
@@ -0,0 +1,73 @@
+//! A per-integration token bucket, backed by Redis so every
+//! sentinel-server replica shares the same budget. Refilled lazily on each
+//! check rather than by a background task: tokens accrued since the
+//! bucket's `updated_at` are added (capped at `capacity`), then the check
+//! takes one if it can.
+
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+#[derive(Clone)]
+pub struct TokenBucket {
+    client: redis::Client,
+}
+
+impl TokenBucket {
+    pub fn new(redis_url: &str) -> Result<Self> {
+        let client = redis::Client::open(redis_url).map_err(|e| Error::Internal(e.into()))?;
+        Ok(Self { client })
+    }
+
+    fn key(tenant_id: Uuid, integration_id: Uuid) -> String {
+        format!("sentinel:policy:bucket:{tenant_id}:{integration_id}")
+    }
+
+    /// Takes one token from `integration_id`'s bucket if one is available
+    /// after refilling at `refill_per_sec` tokens/second, up to `capacity`.
+    /// Returns `true` if a token was taken.
+    pub async fn try_take(
+        &self,
+        tenant_id: Uuid,
+        integration_id: Uuid,
+        capacity: f64,
+        refill_per_sec: f64,
+    ) -> Result<bool> {
+        let mut conn = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        let key = Self::key(tenant_id, integration_id);
+        let now_millis = chrono::Utc::now().timestamp_millis();
+
+        let (stored_tokens, updated_at): (Option<f64>, Option<i64>) = redis::cmd("HMGET")
+            .arg(&key)
+            .arg("tokens")
+            .arg("updated_at")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        let elapsed_secs = updated_at
+            .map(|updated_at| (now_millis - updated_at).max(0) as f64 / 1000.0)
+            .unwrap_or(0.0);
+        let available = (stored_tokens.unwrap_or(capacity) + elapsed_secs * refill_per_sec).min(capacity);
+        let remaining = if available >= 1.0 { available - 1.0 } else { available };
+
+        redis::pipe()
+            .atomic()
+            .hset(&key, "tokens", remaining)
+            .ignore()
+            .hset(&key, "updated_at", now_millis)
+            .ignore()
+            .expire(&key, 3600)
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        Ok(available >= 1.0)
+    }
+}
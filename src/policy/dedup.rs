@@ -0,0 +1,55 @@
+//! Suppresses re-delivering a verdict for a file hash this integration has
+//! already forwarded recently. Keyed on (tenant, integration, hash) so two
+//! different webhooks -- or a webhook and a syslog destination -- each get
+//! their own window rather than suppressing one another.
+
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+#[derive(Clone)]
+pub struct DedupWindow {
+    client: redis::Client,
+}
+
+impl DedupWindow {
+    pub fn new(redis_url: &str) -> Result<Self> {
+        let client = redis::Client::open(redis_url).map_err(|e| Error::Internal(e.into()))?;
+        Ok(Self { client })
+    }
+
+    fn key(tenant_id: Uuid, integration_id: Uuid, file_hash: &str) -> String {
+        format!("sentinel:policy:dedup:{tenant_id}:{integration_id}:{file_hash}")
+    }
+
+    /// Returns `true` the first time `file_hash` is seen for this
+    /// integration within `window_secs`; every repeat inside the window
+    /// returns `false` without extending it, so a burst of re-analyses
+    /// doesn't push the suppression out indefinitely.
+    pub async fn first_seen(
+        &self,
+        tenant_id: Uuid,
+        integration_id: Uuid,
+        file_hash: &str,
+        window_secs: u64,
+    ) -> Result<bool> {
+        let mut conn = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        let key = Self::key(tenant_id, integration_id, file_hash);
+        let set: Option<String> = redis::cmd("SET")
+            .arg(&key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(window_secs)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        Ok(set.is_some())
+    }
+}
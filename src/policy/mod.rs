@@ -0,0 +1,86 @@
+//! Pluggable policy layer gating which verdicts actually reach a delivery
+//! integration (a webhook or a syslog destination), so a flood of benign or
+//! duplicate verdicts doesn't drown a downstream SIEM the way unconditional
+//! delivery would. [`PolicyEngine`] is the extension point a deployment can
+//! swap out; [`DefaultPolicyEngine`] is what ships -- a severity floor, a
+//! per-integration token bucket, and a dedup window keyed on the artifact's
+//! file hash. Every decision it makes is persisted via [`audit`] so an
+//! operator can tell why a verdict never showed up, not just that it didn't.
+
+mod audit;
+mod bucket;
+mod dedup;
+mod rules;
+
+pub use audit::{list_decisions, PolicyDecisionRecord};
+pub use rules::{DefaultPolicyEngine, PolicyConfig};
+
+use uuid::Uuid;
+
+/// Which kind of delivery integration a [`VerdictContext`] is being
+/// evaluated for. Kept distinct from the webhook/syslog ids themselves so
+/// the same `integration_id` value can never collide across the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integration {
+    Webhook,
+    Siem,
+}
+
+impl Integration {
+    fn as_str(self) -> &'static str {
+        match self {
+            Integration::Webhook => "webhook",
+            Integration::Siem => "siem",
+        }
+    }
+}
+
+/// What a verdict looks like to the policy layer -- just enough to
+/// threshold, rate-limit, and dedup on, independent of whether it ends up
+/// JSON-encoded for a webhook or CEF/LEEF-encoded for syslog.
+#[derive(Debug, Clone)]
+pub struct VerdictContext {
+    pub tenant_id: Uuid,
+    pub integration: Integration,
+    pub integration_id: Uuid,
+    pub artifact_id: Uuid,
+    pub file_hash: Option<String>,
+    /// 0-10 scale, matching both `correlation::rules`' `severity: i32` and
+    /// `siem::severity`'s score-derived scale.
+    pub severity: u8,
+}
+
+/// What [`PolicyEngine::evaluate`] hands back: forward the verdict, drop
+/// it, or come back later once whatever held it up (usually a rate limit
+/// window) has passed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeliveryDecision {
+    Send,
+    Suppress { reason: String },
+    Defer { retry_after: std::time::Duration, reason: String },
+}
+
+impl DeliveryDecision {
+    fn label(&self) -> &'static str {
+        match self {
+            DeliveryDecision::Send => "send",
+            DeliveryDecision::Suppress { .. } => "suppress",
+            DeliveryDecision::Defer { .. } => "defer",
+        }
+    }
+
+    fn reason(&self) -> Option<&str> {
+        match self {
+            DeliveryDecision::Send => None,
+            DeliveryDecision::Suppress { reason } | DeliveryDecision::Defer { reason, .. } => Some(reason.as_str()),
+        }
+    }
+}
+
+/// Decides whether a verdict gets forwarded to a given integration.
+/// Implementations are free to ignore rate limiting or dedup entirely --
+/// the trait only promises a decision, not how it got there.
+#[axum::async_trait]
+pub trait PolicyEngine: Send + Sync {
+    async fn evaluate(&self, ctx: &VerdictContext) -> crate::error::Result<DeliveryDecision>;
+}
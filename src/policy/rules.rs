@@ -0,0 +1,99 @@
+//! The policy that ships by default: a severity floor, a per-integration
+//! token bucket, and a dedup window on file hash -- checked in that order,
+//! since there's no point spending a token on a verdict that's getting
+//! suppressed by severity anyway.
+
+use std::time::Duration;
+
+use crate::db::TenantScopedPool;
+use crate::error::Result;
+
+use super::audit;
+use super::bucket::TokenBucket;
+use super::dedup::DedupWindow;
+use super::{DeliveryDecision, PolicyEngine, VerdictContext};
+
+/// Tunables for [`DefaultPolicyEngine`]. `bucket_capacity` and
+/// `rate_limit_per_min` describe the same token bucket -- capacity bounds
+/// how bursty an integration can be, the per-minute rate is how fast it
+/// refills.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyConfig {
+    pub min_severity: u8,
+    pub bucket_capacity: f64,
+    pub rate_limit_per_min: f64,
+    pub dedup_window_secs: u64,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        Self {
+            min_severity: 3,
+            bucket_capacity: 30.0,
+            rate_limit_per_min: 30.0,
+            dedup_window_secs: 3600,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DefaultPolicyEngine {
+    db: TenantScopedPool,
+    bucket: TokenBucket,
+    dedup: DedupWindow,
+    config: PolicyConfig,
+}
+
+impl DefaultPolicyEngine {
+    pub fn new(db: TenantScopedPool, redis_url: &str, config: PolicyConfig) -> Result<Self> {
+        Ok(Self {
+            db,
+            bucket: TokenBucket::new(redis_url)?,
+            dedup: DedupWindow::new(redis_url)?,
+            config,
+        })
+    }
+
+    async fn decide(&self, ctx: &VerdictContext) -> Result<DeliveryDecision> {
+        if ctx.severity < self.config.min_severity {
+            return Ok(DeliveryDecision::Suppress {
+                reason: format!("severity {} below floor {}", ctx.severity, self.config.min_severity),
+            });
+        }
+
+        if let Some(hash) = &ctx.file_hash {
+            let first_seen = self
+                .dedup
+                .first_seen(ctx.tenant_id, ctx.integration_id, hash, self.config.dedup_window_secs)
+                .await?;
+            if !first_seen {
+                return Ok(DeliveryDecision::Suppress {
+                    reason: format!("artifact hash already delivered within {}s", self.config.dedup_window_secs),
+                });
+            }
+        }
+
+        let refill_per_sec = self.config.rate_limit_per_min / 60.0;
+        let took = self
+            .bucket
+            .try_take(ctx.tenant_id, ctx.integration_id, self.config.bucket_capacity, refill_per_sec)
+            .await?;
+        if !took {
+            return Ok(DeliveryDecision::Defer {
+                retry_after: Duration::from_secs(60),
+                reason: "integration rate limit exhausted".to_string(),
+            });
+        }
+
+        Ok(DeliveryDecision::Send)
+    }
+}
+
+#[axum::async_trait]
+impl PolicyEngine for DefaultPolicyEngine {
+    async fn evaluate(&self, ctx: &VerdictContext) -> Result<DeliveryDecision> {
+        let decision = self.decide(ctx).await?;
+        audit::record(&self.db, ctx, &decision).await?;
+        Ok(decision)
+    }
+}
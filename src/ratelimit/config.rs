@@ -0,0 +1,58 @@
+//! Per-tenant rate limit configuration, read from `tenant_rate_limits`.
+
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::Result;
+
+/// Applied when a tenant has no `tenant_rate_limits` row of its own:
+/// 1000 requests/min on the general API (Requirement 11.5), a narrower
+/// 100/min on uploads since those are far more expensive to process, and
+/// a 200 events/sec (burst 500) token bucket on telemetry ingestion --
+/// see [`crate::telemetry`].
+const DEFAULT_API_LIMIT_PER_MINUTE: i32 = 1000;
+const DEFAULT_UPLOAD_LIMIT_PER_MINUTE: i32 = 100;
+const DEFAULT_TELEMETRY_EVENTS_PER_SECOND: f64 = 200.0;
+const DEFAULT_TELEMETRY_BURST: f64 = 500.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct TenantRateLimits {
+    pub api_per_minute: u64,
+    pub upload_per_minute: u64,
+    pub telemetry_events_per_second: f64,
+    pub telemetry_burst: f64,
+}
+
+pub async fn load(db: &TenantScopedPool, tenant_id: Uuid) -> Result<TenantRateLimits> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch(tx, tenant_id))).await
+}
+
+async fn fetch(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid) -> Result<TenantRateLimits> {
+    let row = sqlx::query!(
+        r#"
+        SELECT api_limit_per_minute, upload_limit_per_minute,
+               telemetry_events_per_second, telemetry_burst
+        FROM tenant_rate_limits
+        WHERE tenant_id = $1
+        "#,
+        tenant_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(match row {
+        Some(row) => TenantRateLimits {
+            api_per_minute: row.api_limit_per_minute as u64,
+            upload_per_minute: row.upload_limit_per_minute as u64,
+            telemetry_events_per_second: row.telemetry_events_per_second,
+            telemetry_burst: row.telemetry_burst,
+        },
+        None => TenantRateLimits {
+            api_per_minute: DEFAULT_API_LIMIT_PER_MINUTE as u64,
+            upload_per_minute: DEFAULT_UPLOAD_LIMIT_PER_MINUTE as u64,
+            telemetry_events_per_second: DEFAULT_TELEMETRY_EVENTS_PER_SECOND,
+            telemetry_burst: DEFAULT_TELEMETRY_BURST,
+        },
+    })
+}
@@ -0,0 +1,54 @@
+use axum::extract::{Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use super::config;
+use super::window::{Bucket, Decision};
+use crate::auth::verify_token;
+use crate::error::Error;
+use crate::state::AppState;
+
+const UPLOAD_PATH_PREFIX: &str = "/api/v1/artifacts/upload";
+
+/// Rate-limits every request by the `tenant_id` claim off its
+/// already-signature-verified bearer token, never by the token itself or
+/// the caller's source address -- minting another token or rotating IPs
+/// doesn't move the counter, since both still carry (or fail to carry)
+/// the same tenant_id. A request with no valid token is left to the
+/// normal auth middleware to reject; there's no tenant to charge the
+/// count against.
+pub async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let tenant_id = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .and_then(|token| verify_token(token).ok())
+        .map(|claims| claims.tenant_id);
+
+    let Some(tenant_id) = tenant_id else {
+        return next.run(request).await;
+    };
+
+    let bucket = if request.uri().path().starts_with(UPLOAD_PATH_PREFIX) {
+        Bucket::Upload
+    } else {
+        Bucket::Api
+    };
+
+    let limits = match config::load(&state.db, tenant_id).await {
+        Ok(limits) => limits,
+        Err(e) => return e.into_response(),
+    };
+    let limit = match bucket {
+        Bucket::Api => limits.api_per_minute,
+        Bucket::Upload => limits.upload_per_minute,
+    };
+
+    match state.rate_limiter.check_and_increment(tenant_id, bucket, limit).await {
+        Ok(Decision::Allowed) => next.run(request).await,
+        Ok(Decision::Limited { retry_after_secs }) => Error::RateLimited { retry_after_secs }.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
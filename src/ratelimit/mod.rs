@@ -0,0 +1,19 @@
+//! Per-tenant sliding-window rate limiting.
+//!
+//! A naive limiter keyed on the caller's token or source IP is trivial to
+//! bypass: mint a second token, or rotate the source address, and the
+//! counter resets. Every counter here is keyed on the `tenant_id` claim
+//! off an already signature-verified bearer token instead (see
+//! [`middleware::rate_limit`]), so neither trick moves it -- there's
+//! nothing else the request can present that changes which counter it
+//! hits. [`window`] holds the sliding-window-over-two-fixed-windows
+//! estimator; [`config`] is where a tenant's own limits, if it has any,
+//! are looked up.
+
+mod config;
+mod middleware;
+mod window;
+
+pub use config::{load, TenantRateLimits};
+pub use middleware::rate_limit;
+pub use window::{Decision, RateLimiterStore};
@@ -0,0 +1,93 @@
+//! A sliding window approximated from two fixed windows, backed by
+//! Redis: cheap (one counter per tenant per bucket per minute) while
+//! avoiding the burst-at-the-boundary problem a single fixed window has,
+//! where a caller can double their effective limit by sending a burst
+//! right before and right after a window edge.
+
+use chrono::Utc;
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+const WINDOW_SECS: i64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bucket {
+    Api,
+    Upload,
+}
+
+impl Bucket {
+    fn label(self) -> &'static str {
+        match self {
+            Bucket::Api => "api",
+            Bucket::Upload => "upload",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    Allowed,
+    Limited { retry_after_secs: u64 },
+}
+
+#[derive(Clone)]
+pub struct RateLimiterStore {
+    client: redis::Client,
+}
+
+impl RateLimiterStore {
+    pub fn new(redis_url: &str) -> Result<Self> {
+        let client = redis::Client::open(redis_url).map_err(|e| Error::Internal(e.into()))?;
+        Ok(Self { client })
+    }
+
+    fn key(bucket: Bucket, tenant_id: Uuid, window: i64) -> String {
+        format!("sentinel:ratelimit:{}:{}:{}", bucket.label(), tenant_id, window)
+    }
+
+    /// Estimates `tenant_id`'s rolling request count over the trailing
+    /// `WINDOW_SECS` as `current + previous * (fraction of the previous
+    /// window still inside the rolling interval)`, incrementing the
+    /// current window's counter as part of the same check. Rejects once
+    /// the estimate exceeds `limit`.
+    pub async fn check_and_increment(&self, tenant_id: Uuid, bucket: Bucket, limit: u64) -> Result<Decision> {
+        let now = Utc::now().timestamp();
+        let current_window = now.div_euclid(WINDOW_SECS);
+        let previous_window = current_window - 1;
+        let elapsed_in_current = now - current_window * WINDOW_SECS;
+
+        let mut conn = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        let previous_count: Option<u64> = conn
+            .get(Self::key(bucket, tenant_id, previous_window))
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        let current_key = Self::key(bucket, tenant_id, current_window);
+        let (current_count,): (u64,) = redis::pipe()
+            .atomic()
+            .incr(&current_key, 1u64)
+            .expire(&current_key, WINDOW_SECS * 2)
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        let overlap_fraction = (WINDOW_SECS - elapsed_in_current) as f64 / WINDOW_SECS as f64;
+        let estimate = previous_count.unwrap_or(0) as f64 * overlap_fraction + current_count as f64;
+
+        if estimate > limit as f64 {
+            let retry_after_secs = (WINDOW_SECS - elapsed_in_current).max(1) as u64;
+            Ok(Decision::Limited { retry_after_secs })
+        } else {
+            Ok(Decision::Allowed)
+        }
+    }
+}
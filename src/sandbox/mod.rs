@@ -0,0 +1,17 @@
+//! Network egress policy for the dynamic-analysis sandbox.
+//!
+//! A sandboxed sample's DNS queries and raw connection attempts are meant
+//! to be routed through [`record_dns_query`]/[`record_connection_attempt`]
+//! rather than reaching the real internet directly: each is checked
+//! against the owning tenant's configured allowlist (default-deny unless
+//! listed), the decision is recorded against the artifact's dynamic
+//! report either way, and anything not explicitly allowed is sinkholed so
+//! a sample's beaconing behavior is observable without ever reaching its
+//! real C2 infrastructure. [`router`] exposes the recorded log so
+//! detections can fire on the domains a sample tried to reach.
+
+mod network;
+mod routes;
+
+pub use network::{record_connection_attempt, record_dns_query, Decision, DEFAULT_SINKHOLE_IP};
+pub use routes::router;
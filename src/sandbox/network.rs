@@ -0,0 +1,112 @@
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::Result;
+
+/// Unmatched DNS lookups and connection attempts resolve here by default,
+/// so a sandboxed sample still "connects" somewhere and its beaconing
+/// traffic is observable, instead of the attempt simply failing.
+pub const DEFAULT_SINKHOLE_IP: &str = "10.255.255.1";
+
+/// The outcome of checking a DNS query or connection attempt against the
+/// owning tenant's egress policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Allowed,
+    Sinkholed { to: String },
+}
+
+/// Checks `domain` against `tenant_id`'s configured allowlist (default-deny
+/// unless the domain or one of its parent domains is listed) and records
+/// the decision against `artifact_id`'s dynamic report either way -- a
+/// sinkholed lookup for a suspicious domain is itself a detection signal,
+/// so it's logged exactly like an allowed one.
+pub async fn record_dns_query(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    artifact_id: Uuid,
+    domain: &str,
+) -> Result<Decision> {
+    record_event(db, tenant_id, artifact_id, "dns_query", domain, None).await
+}
+
+/// Checks a raw outbound connection attempt (no DNS query preceded it, or
+/// the sample connected by a hardcoded IP) the same way as
+/// [`record_dns_query`], with `port` recorded alongside it.
+pub async fn record_connection_attempt(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    artifact_id: Uuid,
+    destination: &str,
+    port: u16,
+) -> Result<Decision> {
+    record_event(db, tenant_id, artifact_id, "connection", destination, Some(i32::from(port))).await
+}
+
+async fn record_event(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    artifact_id: Uuid,
+    kind: &str,
+    target: &str,
+    port: Option<i32>,
+) -> Result<Decision> {
+    let kind = kind.to_string();
+    let target = target.to_string();
+
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(async move {
+            let (allowlist, sinkhole_ip) = load_policy(tx, tenant_id).await?;
+            let sinkholed = !is_allowed(&allowlist, &target);
+
+            sqlx::query!(
+                r#"
+                INSERT INTO sandbox_network_events (id, tenant_id, artifact_id, kind, target, port, sinkholed)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+                Uuid::new_v4(),
+                tenant_id,
+                artifact_id,
+                kind,
+                target,
+                port,
+                sinkholed,
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            Ok(if sinkholed {
+                Decision::Sinkholed { to: sinkhole_ip }
+            } else {
+                Decision::Allowed
+            })
+        })
+    })
+    .await
+}
+
+async fn load_policy(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    tenant_id: Uuid,
+) -> Result<(Vec<String>, String)> {
+    let row = sqlx::query!(
+        "SELECT allowlist, sinkhole_ip FROM tenant_network_policies WHERE tenant_id = $1",
+        tenant_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(match row {
+        Some(row) => (row.allowlist, row.sinkhole_ip),
+        None => (Vec::new(), DEFAULT_SINKHOLE_IP.to_string()),
+    })
+}
+
+/// A target is allowed if it exactly matches an allowlist entry or is a
+/// subdomain of one (`c2.example.com` matches an allowlisted
+/// `example.com`).
+fn is_allowed(allowlist: &[String], target: &str) -> bool {
+    allowlist
+        .iter()
+        .any(|allowed| target == allowed || target.ends_with(&format!(".{allowed}")))
+}
@@ -0,0 +1,70 @@
+use axum::extract::{Path, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::error::Result;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize)]
+struct NetworkLogEntry {
+    kind: String,
+    target: String,
+    port: Option<i32>,
+    sinkholed: bool,
+    observed_at: DateTime<Utc>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/api/v1/artifacts/:artifact_id/network-log",
+        get(network_log_handler),
+    )
+}
+
+/// Returns every DNS query and connection attempt recorded for an
+/// artifact's dynamic analysis, in chronological order, so detections can
+/// fire on suspicious domains even when they were sinkholed rather than
+/// actually reached.
+async fn network_log_handler(
+    user: AuthenticatedUser,
+    Path(artifact_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<NetworkLogEntry>>> {
+    user.require_scope_for_resource("artifact:read", artifact_id)?;
+
+    let entries = state
+        .db
+        .with_tenant(user.tenant_id, |tx| {
+            Box::pin(async move {
+                let rows = sqlx::query!(
+                    r#"
+                    SELECT kind, target, port, sinkholed, observed_at
+                    FROM sandbox_network_events
+                    WHERE artifact_id = $1
+                    ORDER BY observed_at
+                    "#,
+                    artifact_id
+                )
+                .fetch_all(&mut **tx)
+                .await?;
+
+                Ok(rows
+                    .into_iter()
+                    .map(|row| NetworkLogEntry {
+                        kind: row.kind,
+                        target: row.target,
+                        port: row.port,
+                        sinkholed: row.sinkholed,
+                        observed_at: row.observed_at,
+                    })
+                    .collect())
+            })
+        })
+        .await?;
+
+    Ok(Json(entries))
+}
@@ -0,0 +1,193 @@
+use sqlx::{Postgres, Transaction};
+use umbral_pre::{Capsule, CapsuleFrag, PublicKey, Signer};
+use uuid::Uuid;
+
+use super::keyring::UmbralKeyRing;
+use crate::crypto::{current_tenant_dek, Kek, TenantDek};
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+
+const THRESHOLD: usize = 1;
+const SHARES: usize = 1;
+
+/// Authorizes `grantee_tenant_id` to read `artifact_id`, owned by
+/// `owner_tenant_id`: snapshots the owner's current DEK, Umbral-encrypts
+/// it under the owner's own public key, and applies a one-time transform
+/// key (owner -> grantee) to produce a capsule fragment only the grantee's
+/// secret key can use to recover the DEK. The plaintext artifact itself is
+/// never touched.
+pub async fn create_grant(
+    db: &TenantScopedPool,
+    kek: &Kek,
+    keyring: &UmbralKeyRing,
+    owner_tenant_id: Uuid,
+    grantee_tenant_id: Uuid,
+    artifact_id: Uuid,
+) -> Result<()> {
+    if owner_tenant_id == grantee_tenant_id {
+        return Err(Error::BadRequest(
+            "cannot grant artifact access to your own tenant".to_string(),
+        ));
+    }
+
+    let (owner_pk, owner_sk) = keyring.get_or_create(owner_tenant_id);
+    let (grantee_pk, _grantee_sk) = keyring.get_or_create(grantee_tenant_id);
+    let keyring_instance_id = keyring.instance_id();
+    let kek = kek.clone();
+
+    db.with_tenant(owner_tenant_id, |tx| {
+        Box::pin(async move {
+            sqlx::query!("SELECT id FROM artifacts WHERE id = $1", artifact_id)
+                .fetch_optional(&mut **tx)
+                .await?
+                .ok_or(Error::NotFound)?;
+
+            let (dek, _key_version) = current_tenant_dek(tx, &kek, owner_tenant_id).await?;
+
+            let (capsule, dek_ciphertext) = umbral_pre::encrypt(&owner_pk, dek.as_bytes())
+                .map_err(|e| Error::Internal(anyhow::anyhow!("failed to seal data key for sharing: {e}")))?;
+
+            let signer = Signer::new(owner_sk.clone());
+            let kfrags = umbral_pre::generate_kfrags(
+                &owner_sk,
+                &grantee_pk,
+                &signer,
+                THRESHOLD,
+                SHARES,
+                false,
+                false,
+            );
+            let kfrag = kfrags
+                .into_vec()
+                .pop()
+                .expect("generate_kfrags(shares=1) always returns one fragment");
+            let cfrag = umbral_pre::reencrypt(&capsule, kfrag);
+
+            let capsule_bytes =
+                serde_json::to_vec(&capsule).map_err(|e| Error::Internal(anyhow::anyhow!(e)))?;
+            let cfrag_bytes = serde_json::to_vec(&cfrag.unverify())
+                .map_err(|e| Error::Internal(anyhow::anyhow!(e)))?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO artifact_grants
+                    (artifact_id, grantee_tenant_id, owner_tenant_id, owner_public_key, capsule, dek_ciphertext, capsule_frag, keyring_instance_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (artifact_id, grantee_tenant_id) DO UPDATE SET
+                    owner_public_key = EXCLUDED.owner_public_key,
+                    capsule = EXCLUDED.capsule,
+                    dek_ciphertext = EXCLUDED.dek_ciphertext,
+                    capsule_frag = EXCLUDED.capsule_frag,
+                    keyring_instance_id = EXCLUDED.keyring_instance_id,
+                    created_at = NOW()
+                "#,
+                artifact_id,
+                grantee_tenant_id,
+                owner_tenant_id,
+                owner_pk.to_compressed_bytes().to_vec(),
+                capsule_bytes,
+                dek_ciphertext.to_vec(),
+                cfrag_bytes,
+                keyring_instance_id,
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+/// Recovers the DEK a grant re-wrapped for `grantee_tenant_id`, for
+/// opening `artifact_id` on the grantee's behalf: loads the capsule,
+/// capsule fragment, ciphertext, and owner's public key snapshotted at
+/// grant time, then runs Umbral's decrypt-reencrypted with the grantee's
+/// own secret key. The capsule fragment came from our own `create_grant`,
+/// not an untrusted relay, so it's taken via
+/// [`CapsuleFrag::skip_verification`] rather than re-verified against a
+/// signer key nothing here persists. Fails with [`Error::NotFound`] if no
+/// grant exists for this artifact/tenant pair -- including one that was
+/// revoked, and with [`Error::UnprocessableEntity`] if the grant was
+/// sealed by a keyring instance that isn't this one (see
+/// [`super::keyring`]) -- its keys are gone for good, and decrypting
+/// would fail anyway, so this fails fast with a cause the owner can act
+/// on (recreate the grant) instead of a generic crypto error.
+pub async fn recover_grant_key(
+    tx: &mut Transaction<'_, Postgres>,
+    keyring: &UmbralKeyRing,
+    artifact_id: Uuid,
+    grantee_tenant_id: Uuid,
+) -> Result<TenantDek> {
+    let row = sqlx::query!(
+        r#"
+        SELECT owner_public_key, capsule, dek_ciphertext, capsule_frag, keyring_instance_id
+        FROM artifact_grants
+        WHERE artifact_id = $1 AND grantee_tenant_id = $2
+        "#,
+        artifact_id,
+        grantee_tenant_id,
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or(Error::NotFound)?;
+
+    if row.keyring_instance_id != keyring.instance_id() {
+        return Err(Error::UnprocessableEntity(
+            "this grant's sharing keypair no longer exists (the instance that created it has \
+             since restarted, or this request landed on a different replica); ask the owner to \
+             revoke and recreate the grant"
+                .to_string(),
+        ));
+    }
+
+    let (_grantee_pk, grantee_sk) = keyring.get_or_create(grantee_tenant_id);
+
+    let owner_pk = PublicKey::try_from_compressed_bytes(&row.owner_public_key)
+        .map_err(|e| Error::Internal(anyhow::anyhow!("grant has an invalid owner public key: {e}")))?;
+    let capsule: Capsule =
+        serde_json::from_slice(&row.capsule).map_err(|e| Error::Internal(anyhow::anyhow!(e)))?;
+    let cfrag: CapsuleFrag =
+        serde_json::from_slice(&row.capsule_frag).map_err(|e| Error::Internal(anyhow::anyhow!(e)))?;
+
+    let dek_bytes = umbral_pre::decrypt_reencrypted(
+        &grantee_sk,
+        &owner_pk,
+        &capsule,
+        [cfrag.skip_verification()],
+        &row.dek_ciphertext,
+    )
+    .map_err(|e| Error::Internal(anyhow::anyhow!("failed to recover data key for grant: {e}")))?;
+
+    let dek_bytes: [u8; crate::crypto::DEK_LEN] = dek_bytes
+        .as_ref()
+        .try_into()
+        .map_err(|_| Error::Internal(anyhow::anyhow!("recovered data key has the wrong length")))?;
+
+    Ok(TenantDek::new(dek_bytes))
+}
+
+/// Revokes a previously issued grant, immediately restoring denial:
+/// `artifacts` has no policy that admits the grantee once this row is
+/// gone. Revoking a grant that doesn't exist (or was already revoked) is
+/// not an error.
+pub async fn revoke_grant(
+    db: &TenantScopedPool,
+    owner_tenant_id: Uuid,
+    grantee_tenant_id: Uuid,
+    artifact_id: Uuid,
+) -> Result<()> {
+    db.with_tenant(owner_tenant_id, |tx| {
+        Box::pin(async move {
+            sqlx::query!(
+                "DELETE FROM artifact_grants WHERE artifact_id = $1 AND grantee_tenant_id = $2",
+                artifact_id,
+                grantee_tenant_id
+            )
+            .execute(&mut **tx)
+            .await?;
+            Ok(())
+        })
+    })
+    .await
+}
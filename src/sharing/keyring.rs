@@ -0,0 +1,76 @@
+//! In-memory per-tenant Umbral keypairs.
+//!
+//! `umbral_pre::SecretKey` deliberately has no public way to be
+//! reconstructed from raw bytes (the crate wraps it in a private,
+//! non-exportable secret container), so unlike the tenant DEKs in
+//! `crate::crypto` these keypairs are never wrapped and written to the
+//! database -- they're generated once per tenant on first use and kept
+//! only in memory for the life of the process. A capsule fragment handed
+//! out in a grant is only usable by a grantee that still holds the same
+//! keypair it was produced for.
+//!
+//! **Deployment constraint:** because keys live only in one process's
+//! memory, every request that touches sharing -- `create_grant`,
+//! `revoke_grant`, and a grantee's `storage::download` -- has to land on
+//! the same replica that generated the tenant's keypair, for as long as
+//! that process runs. Behind a load balancer with more than one replica,
+//! or across a restart, a keypair a grant was created against can be gone
+//! for good; until keys are wrapped under the deployment
+//! [`crate::crypto::Kek`] and persisted the way tenant DEKs are (blocked
+//! on `umbral_pre::SecretKey` exposing a way back in from raw bytes),
+//! sharing is only safe to run as a single long-lived instance, or
+//! pinned per-tenant with sticky routing.
+//!
+//! That can't be fixed here, so [`instance_id`](UmbralKeyRing::instance_id)
+//! at least turns it into a loud, specific failure instead of a silent
+//! one: every ring generates a random id at construction, every grant
+//! records which ring's keypair it was sealed against (see
+//! [`super::grants::create_grant`]), and [`super::grants::recover_grant_key`]
+//! refuses a grant stamped with a different id rather than feeding a
+//! secret key that was never the matching half of the grant's capsule
+//! into Umbral and surfacing whatever generic error falls out of that.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use umbral_pre::{PublicKey, SecretKey};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct UmbralKeyRing {
+    instance_id: Uuid,
+    keys: Arc<Mutex<HashMap<Uuid, SecretKey>>>,
+}
+
+impl Default for UmbralKeyRing {
+    fn default() -> Self {
+        Self {
+            instance_id: Uuid::new_v4(),
+            keys: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl UmbralKeyRing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Identifies this specific ring (and so, implicitly, this specific
+    /// process's in-memory keys) so a grant can record which one its
+    /// capsule fragment was sealed against.
+    pub fn instance_id(&self) -> Uuid {
+        self.instance_id
+    }
+
+    /// Returns `tenant_id`'s keypair, generating one the first time it's
+    /// asked for.
+    pub fn get_or_create(&self, tenant_id: Uuid) -> (PublicKey, SecretKey) {
+        let mut keys = self.keys.lock().unwrap();
+        let sk = keys
+            .entry(tenant_id)
+            .or_insert_with(SecretKey::random)
+            .clone();
+        (sk.public_key(), sk)
+    }
+}
@@ -0,0 +1,17 @@
+//! Controlled cross-tenant artifact sharing via explicit grants.
+//!
+//! A grant authorizes exactly one other tenant to read exactly one
+//! artifact. Rather than handing the grantee a copy of the owner's DEK
+//! directly, the owner's tenant re-wraps its current DEK for the
+//! grantee's Umbral public key via a transform (proxy re-)encryption: the
+//! server computes a capsule fragment from a one-time transform key, but
+//! it is never in possession of the grantee's secret key and never
+//! decrypts the artifact's ciphertext to do it (see [`grants`]).
+
+mod grants;
+mod keyring;
+mod routes;
+
+pub use grants::{create_grant, recover_grant_key, revoke_grant};
+pub use keyring::UmbralKeyRing;
+pub use routes::router;
@@ -0,0 +1,51 @@
+use axum::extract::{Path, State};
+use axum::routing::{delete, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::grants::{create_grant, revoke_grant};
+use crate::auth::AuthenticatedUser;
+use crate::error::Result;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateGrantRequest {
+    pub grantee_tenant_id: Uuid,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/artifacts/:artifact_id/grants", post(create_grant_handler))
+        .route(
+            "/api/v1/artifacts/:artifact_id/grants/:grantee_tenant_id",
+            delete(revoke_grant_handler),
+        )
+}
+
+async fn create_grant_handler(
+    user: AuthenticatedUser,
+    Path(artifact_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Json(body): Json<CreateGrantRequest>,
+) -> Result<()> {
+    user.require_scope_for_resource("artifact:write", artifact_id)?;
+    create_grant(
+        &state.db,
+        &state.kek,
+        &state.sharing,
+        user.tenant_id,
+        body.grantee_tenant_id,
+        artifact_id,
+    )
+    .await
+}
+
+async fn revoke_grant_handler(
+    user: AuthenticatedUser,
+    Path((artifact_id, grantee_tenant_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+) -> Result<()> {
+    user.require_scope_for_resource("artifact:write", artifact_id)?;
+    revoke_grant(&state.db, user.tenant_id, grantee_tenant_id, artifact_id).await
+}
@@ -0,0 +1,95 @@
+//! CEF (Common Event Format) serialization --
+//! `Version|Device Vendor|Device Product|Device Version|Signature ID|Name|Severity|Extension`.
+//! Header fields escape `\`, `|`, and newlines; extension values escape
+//! `\`, `=`, and newlines -- the backslash has to go first in both cases
+//! so it doesn't double-escape the characters inserted for the others.
+
+use super::SiemEvent;
+
+const DEVICE_VENDOR: &str = "Sentinel";
+const DEVICE_PRODUCT: &str = "EDR";
+const DEVICE_VERSION: &str = env!("CARGO_PKG_VERSION");
+const SIGNATURE_ID: &str = "VERDICT";
+
+/// Formats `event` as one `CEF:0` line, ready to ship over syslog.
+pub fn to_cef(event: &SiemEvent) -> String {
+    let severity = super::severity(event.score);
+
+    let mut extensions = vec![
+        ("cs1".to_string(), escape_extension(&event.artifact_id.to_string())),
+        ("cs1Label".to_string(), "artifactId".to_string()),
+    ];
+    if let Some(hash) = &event.file_hash {
+        extensions.push(("fileHash".to_string(), escape_extension(hash)));
+    }
+    if let Some(name) = &event.file_name {
+        extensions.push(("fname".to_string(), escape_extension(name)));
+    }
+    if let Some(action) = &event.action {
+        extensions.push(("act".to_string(), escape_extension(action)));
+    }
+
+    let extension = extensions
+        .into_iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+        escape_header(DEVICE_VENDOR),
+        escape_header(DEVICE_PRODUCT),
+        escape_header(DEVICE_VERSION),
+        escape_header(SIGNATURE_ID),
+        escape_header(&event.verdict),
+        severity,
+        extension,
+    )
+}
+
+fn escape_header(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\n', '\r'], "\\n")
+}
+
+fn escape_extension(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace(['\n', '\r'], "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> SiemEvent {
+        SiemEvent {
+            artifact_id: "1e1c0c6e-8f8a-4b1a-9a3f-3f0f1b6f1a11".parse().unwrap(),
+            verdict: "malicious".to_string(),
+            score: 0.9,
+            file_hash: Some("deadbeef".to_string()),
+            file_name: Some("invoice.pdf.exe".to_string()),
+            action: Some("quarantined".to_string()),
+        }
+    }
+
+    #[test]
+    fn to_cef_formats_header_and_extension() {
+        let line = to_cef(&event());
+        assert!(line.starts_with(&format!("CEF:0|Sentinel|EDR|{}|VERDICT|malicious|9|", DEVICE_VERSION)));
+        assert!(line.contains("fileHash=deadbeef"));
+        assert!(line.contains("fname=invoice.pdf.exe"));
+        assert!(line.contains("act=quarantined"));
+    }
+
+    #[test]
+    fn escape_header_escapes_backslash_before_pipe_and_newline() {
+        assert_eq!(escape_header("a\\b|c\nd"), "a\\\\b\\|c\\nd");
+    }
+
+    #[test]
+    fn escape_extension_escapes_backslash_before_equals_and_newline() {
+        assert_eq!(escape_extension("a\\b=c\nd"), "a\\\\b\\=c\\nd");
+    }
+}
@@ -0,0 +1,165 @@
+//! CRUD for a tenant's registered syslog collector endpoints, backed by
+//! `tenant_syslog_destinations`.
+
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use super::syslog::Transport;
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+
+/// Which format a destination wants its verdicts serialized as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Cef,
+    Leef,
+}
+
+impl Format {
+    fn as_str(self) -> &'static str {
+        match self {
+            Format::Cef => "cef",
+            Format::Leef => "leef",
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "cef" => Ok(Format::Cef),
+            "leef" => Ok(Format::Leef),
+            other => Err(Error::BadRequest(format!("unsupported siem format: {other}"))),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyslogDestinationRegistration {
+    pub id: Uuid,
+    pub host: String,
+    pub port: i32,
+    pub transport: String,
+    pub format: String,
+    pub enabled: bool,
+}
+
+pub async fn register(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    host: String,
+    port: i32,
+    transport: String,
+    format: String,
+) -> Result<SyslogDestinationRegistration> {
+    let transport = Transport::parse(&transport)?;
+    let format = Format::parse(&format)?;
+
+    let id = Uuid::new_v4();
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(insert(tx, id, tenant_id, host.clone(), port, transport, format))
+    })
+    .await?;
+
+    Ok(SyslogDestinationRegistration {
+        id,
+        host,
+        port,
+        transport: transport.as_str().to_string(),
+        format: format.as_str().to_string(),
+        enabled: true,
+    })
+}
+
+async fn insert(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    tenant_id: Uuid,
+    host: String,
+    port: i32,
+    transport: Transport,
+    format: Format,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO tenant_syslog_destinations (id, tenant_id, host, port, transport, format) VALUES ($1, $2, $3, $4, $5, $6)",
+        id,
+        tenant_id,
+        host,
+        port,
+        transport.as_str(),
+        format.as_str(),
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn list(db: &TenantScopedPool, tenant_id: Uuid) -> Result<Vec<SyslogDestinationRegistration>> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_all(tx, tenant_id))).await
+}
+
+async fn fetch_all(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid) -> Result<Vec<SyslogDestinationRegistration>> {
+    let rows = sqlx::query!(
+        "SELECT id, host, port, transport, format, enabled FROM tenant_syslog_destinations WHERE tenant_id = $1",
+        tenant_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SyslogDestinationRegistration {
+            id: row.id,
+            host: row.host,
+            port: row.port,
+            transport: row.transport,
+            format: row.format,
+            enabled: row.enabled,
+        })
+        .collect())
+}
+
+pub async fn delete(db: &TenantScopedPool, tenant_id: Uuid, destination_id: Uuid) -> Result<()> {
+    db.with_tenant(tenant_id, |tx| Box::pin(remove(tx, destination_id))).await
+}
+
+async fn remove(tx: &mut Transaction<'_, Postgres>, destination_id: Uuid) -> Result<()> {
+    sqlx::query!("DELETE FROM tenant_syslog_destinations WHERE id = $1", destination_id)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// The rows [`super::dispatcher::SiemDispatcher`] needs to fan a verdict
+/// out to every enabled destination for a tenant.
+pub(super) struct SyslogTarget {
+    pub id: Uuid,
+    pub host: String,
+    pub port: i32,
+    pub transport: Transport,
+    pub format: Format,
+}
+
+pub(super) async fn enabled_targets(db: &TenantScopedPool, tenant_id: Uuid) -> Result<Vec<SyslogTarget>> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_enabled(tx, tenant_id))).await
+}
+
+async fn fetch_enabled(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid) -> Result<Vec<SyslogTarget>> {
+    let rows = sqlx::query!(
+        "SELECT id, host, port, transport, format FROM tenant_syslog_destinations WHERE tenant_id = $1 AND enabled",
+        tenant_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(SyslogTarget {
+                id: row.id,
+                host: row.host,
+                port: row.port,
+                transport: Transport::parse(&row.transport)?,
+                format: Format::parse(&row.format)?,
+            })
+        })
+        .collect()
+}
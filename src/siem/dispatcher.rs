@@ -0,0 +1,97 @@
+//! Fans a [`SiemEvent`] out to every syslog collector a tenant has
+//! registered, formatting it per destination (see [`super::config`]).
+//! Every destination is run past a [`PolicyEngine`] first so a severity
+//! floor, rate limit, or dedup rule can keep a noisy verdict from ever
+//! being sent.
+
+use std::sync::Arc;
+
+use tokio::net::lookup_host;
+use uuid::Uuid;
+
+use super::config::{self, Format};
+use super::syslog::Transport;
+use super::syslog::SyslogClient;
+use super::SiemEvent;
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+use crate::policy::{DeliveryDecision, Integration, PolicyEngine, VerdictContext};
+
+/// Fans a [`SiemEvent`] out to every syslog destination a tenant has
+/// registered.
+#[derive(Clone)]
+pub struct SiemDispatcher {
+    db: TenantScopedPool,
+    policy: Arc<dyn PolicyEngine>,
+}
+
+impl SiemDispatcher {
+    pub fn new(db: TenantScopedPool, policy: Arc<dyn PolicyEngine>) -> Self {
+        Self { db, policy }
+    }
+
+    /// Looks up `tenant_id`'s enabled syslog destinations and, for each one
+    /// the policy engine doesn't suppress, spawns one delivery, so a slow
+    /// or unreachable collector never blocks the others or the caller --
+    /// the same fire-and-forget shape as
+    /// [`crate::webhooks::WebhookDispatcher::dispatch`].
+    pub async fn dispatch(&self, tenant_id: Uuid, event: &SiemEvent) -> Result<()> {
+        let targets = config::enabled_targets(&self.db, tenant_id).await?;
+
+        for target in targets {
+            let ctx = VerdictContext {
+                tenant_id,
+                integration: Integration::Siem,
+                integration_id: target.id,
+                artifact_id: event.artifact_id,
+                file_hash: event.file_hash.clone(),
+                severity: super::severity(event.score),
+            };
+
+            let message = match target.format {
+                Format::Cef => super::to_cef(event),
+                Format::Leef => super::to_leef(event),
+            };
+
+            match self.policy.evaluate(&ctx).await? {
+                DeliveryDecision::Suppress { reason } => {
+                    tracing::info!(destination_id = %target.id, reason, "syslog delivery suppressed by policy");
+                }
+                DeliveryDecision::Defer { retry_after, reason } => {
+                    tracing::info!(destination_id = %target.id, reason, "syslog delivery deferred by policy");
+                    tokio::spawn(async move {
+                        tokio::time::sleep(retry_after).await;
+                        deliver(target.host, target.port, target.transport, message).await;
+                    });
+                }
+                DeliveryDecision::Send => {
+                    tokio::spawn(deliver(target.host, target.port, target.transport, message));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn deliver(host: String, port: i32, transport: Transport, message: String) {
+    let addr = match lookup_host((host.as_str(), port as u16)).await.ok().and_then(|mut i| i.next()) {
+        Some(addr) => addr,
+        None => {
+            tracing::warn!(%host, port, "could not resolve syslog collector address");
+            return;
+        }
+    };
+
+    let client = SyslogClient { addr, transport };
+    if let Err(error) = client.send(&message).await {
+        tracing::warn!(%host, port, %error, "syslog delivery failed");
+    }
+}
+
+/// Parses a user-supplied port into the range a [`SyslogClient`] needs --
+/// kept here rather than in [`super::routes`] so the error message stays
+/// consistent with [`super::config::register`]'s other validation.
+pub(super) fn parse_port(port: i32) -> Result<u16> {
+    u16::try_from(port).map_err(|_| Error::BadRequest(format!("invalid port: {port}")))
+}
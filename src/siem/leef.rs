@@ -0,0 +1,77 @@
+//! LEEF (Log Event Extended Format, v2.0) serialization --
+//! `LEEF:2.0|Vendor|Product|Version|EventID|` followed by tab-delimited
+//! `key=value` attributes. Values escape the same characters as CEF
+//! extensions (`\`, `=`, newlines); only the attribute delimiter differs.
+
+use super::SiemEvent;
+
+const VENDOR: &str = "Sentinel";
+const PRODUCT: &str = "EDR";
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const EVENT_ID: &str = "VERDICT";
+
+/// Formats `event` as one `LEEF:2.0` line, ready to ship over syslog.
+pub fn to_leef(event: &SiemEvent) -> String {
+    let severity = super::severity(event.score);
+
+    let mut attributes = vec![
+        ("sev".to_string(), severity.to_string()),
+        ("cat".to_string(), escape(&event.verdict)),
+        ("cs1".to_string(), escape(&event.artifact_id.to_string())),
+        ("cs1Label".to_string(), "artifactId".to_string()),
+    ];
+    if let Some(hash) = &event.file_hash {
+        attributes.push(("fileHash".to_string(), escape(hash)));
+    }
+    if let Some(name) = &event.file_name {
+        attributes.push(("fname".to_string(), escape(name)));
+    }
+    if let Some(action) = &event.action {
+        attributes.push(("act".to_string(), escape(action)));
+    }
+
+    let attribute_str = attributes
+        .into_iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("\t");
+
+    format!("LEEF:2.0|{VENDOR}|{PRODUCT}|{VERSION}|{EVENT_ID}|{attribute_str}")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace(['\n', '\r'], "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> SiemEvent {
+        SiemEvent {
+            artifact_id: "1e1c0c6e-8f8a-4b1a-9a3f-3f0f1b6f1a11".parse().unwrap(),
+            verdict: "malicious".to_string(),
+            score: 0.9,
+            file_hash: Some("deadbeef".to_string()),
+            file_name: Some("invoice.pdf.exe".to_string()),
+            action: Some("quarantined".to_string()),
+        }
+    }
+
+    #[test]
+    fn to_leef_formats_header_and_tab_delimited_attributes() {
+        let line = to_leef(&event());
+        assert!(line.starts_with(&format!("LEEF:2.0|Sentinel|EDR|{}|VERDICT|", VERSION)));
+        assert!(line.contains("sev=9\tcat=malicious"));
+        assert!(line.contains("fileHash=deadbeef"));
+        assert!(line.contains("fname=invoice.pdf.exe"));
+        assert!(line.contains("act=quarantined"));
+    }
+
+    #[test]
+    fn escape_escapes_backslash_before_equals_and_newline() {
+        assert_eq!(escape("a\\b=c\nd"), "a\\\\b\\=c\\nd");
+    }
+}
@@ -0,0 +1,58 @@
+//! CEF/LEEF serialization and syslog (RFC 5424) delivery, for SIEMs that
+//! ingest those formats directly rather than the JSON/HMAC channel in
+//! [`crate::webhooks`].
+//!
+//! [`SiemEvent`] is the shape a verdict would be mapped into to produce
+//! either format -- mirrors fields (file hash, filename, the action
+//! taken) that nothing in this tree generates yet, the same gap
+//! [`crate::webhooks::AlertPayload`] documents for its own fields.
+
+mod cef;
+mod config;
+mod dispatcher;
+mod leef;
+mod routes;
+mod syslog;
+
+pub use cef::to_cef;
+pub use config::SyslogDestinationRegistration;
+pub use dispatcher::SiemDispatcher;
+pub use leef::to_leef;
+pub use routes::router;
+
+use uuid::Uuid;
+
+/// A verdict, mapped into the fields CEF/LEEF extensions carry.
+#[derive(Debug, Clone)]
+pub struct SiemEvent {
+    pub artifact_id: Uuid,
+    pub verdict: String,
+    pub score: f64,
+    pub file_hash: Option<String>,
+    pub file_name: Option<String>,
+    pub action: Option<String>,
+}
+
+/// Derives a 0-10 CEF/LEEF severity from a 0.0-1.0 verdict score -- both
+/// formats use the same linear scale.
+pub(crate) fn severity(score: f64) -> u8 {
+    (score.clamp(0.0, 1.0) * 10.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_scales_linearly() {
+        assert_eq!(severity(0.0), 0);
+        assert_eq!(severity(0.5), 5);
+        assert_eq!(severity(1.0), 10);
+    }
+
+    #[test]
+    fn severity_clamps_out_of_range_scores() {
+        assert_eq!(severity(-1.0), 0);
+        assert_eq!(severity(2.0), 10);
+    }
+}
@@ -0,0 +1,92 @@
+use axum::extract::{Path, State};
+use axum::routing::{delete, get};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::config::{self, SyslogDestinationRegistration};
+use super::dispatcher;
+use crate::auth::AuthenticatedUser;
+use crate::error::Result;
+use crate::policy::{self, Integration, PolicyDecisionRecord};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterSyslogDestinationRequest {
+    pub host: String,
+    pub port: i32,
+    pub transport: String,
+    pub format: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPolicyDecisionsResponse {
+    pub decisions: Vec<PolicyDecisionRecord>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/siem/syslog",
+            get(list_destinations_handler).post(register_destination_handler),
+        )
+        .route(
+            "/api/v1/siem/syslog/:destination_id",
+            delete(delete_destination_handler),
+        )
+        .route(
+            "/api/v1/siem/syslog/:destination_id/policy-decisions",
+            get(list_policy_decisions_handler),
+        )
+}
+
+async fn register_destination_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Json(body): Json<RegisterSyslogDestinationRequest>,
+) -> Result<Json<SyslogDestinationRegistration>> {
+    user.require_scope("siem:write")?;
+    dispatcher::parse_port(body.port)?;
+
+    let registration = config::register(
+        &state.db,
+        user.tenant_id,
+        body.host,
+        body.port,
+        body.transport,
+        body.format,
+    )
+    .await?;
+    Ok(Json(registration))
+}
+
+async fn list_destinations_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SyslogDestinationRegistration>>> {
+    user.require_scope("siem:read")?;
+    let destinations = config::list(&state.db, user.tenant_id).await?;
+    Ok(Json(destinations))
+}
+
+async fn delete_destination_handler(
+    user: AuthenticatedUser,
+    Path(destination_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<()> {
+    user.require_scope("siem:write")?;
+    config::delete(&state.db, user.tenant_id, destination_id).await
+}
+
+/// Lists the most recent Send/Suppress/Defer decisions the policy engine
+/// made for this syslog destination, most recent first -- see
+/// [`crate::policy`] for what drives them.
+async fn list_policy_decisions_handler(
+    user: AuthenticatedUser,
+    Path(destination_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<ListPolicyDecisionsResponse>> {
+    user.require_scope("siem:read")?;
+    let decisions = policy::list_decisions(&state.db, user.tenant_id, Integration::Siem, destination_id).await?;
+    Ok(Json(ListPolicyDecisionsResponse { decisions }))
+}
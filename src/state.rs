@@ -0,0 +1,33 @@
+use axum::extract::FromRef;
+
+use crate::auth::revocation::RevocationStore;
+use crate::correlation::CorrelationEngine;
+use crate::crypto::Kek;
+use crate::db::TenantScopedPool;
+use crate::ratelimit::RateLimiterStore;
+use crate::sharing::UmbralKeyRing;
+use crate::siem::SiemDispatcher;
+use crate::telemetry::TelemetryBucket;
+use crate::verdicts::VerdictBroadcaster;
+use crate::webhooks::WebhookDispatcher;
+
+/// Shared state handed to every axum handler via `State<AppState>`.
+#[derive(Clone)]
+pub struct AppState {
+    pub db: TenantScopedPool,
+    pub verdicts: VerdictBroadcaster,
+    pub kek: Kek,
+    pub sharing: UmbralKeyRing,
+    pub revocation: RevocationStore,
+    pub rate_limiter: RateLimiterStore,
+    pub telemetry_bucket: TelemetryBucket,
+    pub webhooks: WebhookDispatcher,
+    pub siem: SiemDispatcher,
+    pub correlation: CorrelationEngine,
+}
+
+impl FromRef<AppState> for RevocationStore {
+    fn from_ref(state: &AppState) -> Self {
+        state.revocation.clone()
+    }
+}
@@ -0,0 +1,111 @@
+//! A Redis-Lua-backed token bucket for telemetry ingestion.
+//!
+//! The sliding window in `crate::ratelimit::window` estimates a rolling
+//! count across two fixed windows -- a reasonable fit for bursty human API
+//! traffic, but a poor one for a steady stream of agent telemetry, which is
+//! better described directly by a sustained rate plus a burst allowance.
+//! Refilling and withdrawing have to happen as one atomic step or two
+//! concurrent ingestion requests could both read the same token count and
+//! over-admit, so this is a single round trip through a Lua script rather
+//! than a multi-command pipeline.
+
+use redis::Script;
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::ratelimit::Decision;
+
+const SCRIPT: &str = r#"
+local bucket_key = KEYS[1]
+local rate = tonumber(ARGV[1])
+local burst = tonumber(ARGV[2])
+local requested = tonumber(ARGV[3])
+local now = tonumber(ARGV[4])
+
+local fields = redis.call('HMGET', bucket_key, 'tokens', 'ts')
+local tokens = tonumber(fields[1])
+local last_ts = tonumber(fields[2])
+
+if tokens == nil then
+    tokens = burst
+    last_ts = now
+end
+
+local elapsed = now - last_ts
+if elapsed < 0 then
+    elapsed = 0
+end
+tokens = math.min(burst, tokens + elapsed * rate)
+
+local allowed = 0
+if tokens >= requested then
+    tokens = tokens - requested
+    allowed = 1
+end
+
+redis.call('HMSET', bucket_key, 'tokens', tokens, 'ts', now)
+redis.call('EXPIRE', bucket_key, 3600)
+
+return { allowed, tostring(tokens) }
+"#;
+
+#[derive(Clone)]
+pub struct TelemetryBucket {
+    client: redis::Client,
+    script: Script,
+}
+
+impl TelemetryBucket {
+    pub fn new(redis_url: &str) -> Result<Self> {
+        let client = redis::Client::open(redis_url).map_err(|e| Error::Internal(e.into()))?;
+        Ok(Self {
+            client,
+            script: Script::new(SCRIPT),
+        })
+    }
+
+    fn key(tenant_id: Uuid, endpoint_id: Uuid) -> String {
+        format!("sentinel:telemetry_bucket:{tenant_id}:{endpoint_id}")
+    }
+
+    /// Atomically refills `endpoint_id`'s bucket for the time elapsed since
+    /// its last request, then tries to withdraw `requested` tokens (one per
+    /// event in the batch being admitted). Rejects the whole batch if that
+    /// would overdraw it; `retry_after_secs` is how long until `requested`
+    /// tokens would be available again at the configured `rate`.
+    pub async fn check_and_consume(
+        &self,
+        tenant_id: Uuid,
+        endpoint_id: Uuid,
+        rate: f64,
+        burst: f64,
+        requested: f64,
+    ) -> Result<Decision> {
+        let mut conn = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        let now = chrono::Utc::now().timestamp() as f64;
+
+        let (allowed, remaining): (i64, f64) = self
+            .script
+            .key(Self::key(tenant_id, endpoint_id))
+            .arg(rate)
+            .arg(burst)
+            .arg(requested)
+            .arg(now)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::Internal(e.into()))?;
+
+        if allowed == 1 {
+            Ok(Decision::Allowed)
+        } else {
+            let deficit = requested - remaining;
+            let retry_after_secs = (deficit / rate).ceil().max(1.0) as u64;
+            Ok(Decision::Limited { retry_after_secs })
+        }
+    }
+}
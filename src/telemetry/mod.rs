@@ -0,0 +1,22 @@
+//! Endpoint telemetry ingestion (EDR agent events).
+//!
+//! Agents authenticate by mTLS client certificate rather than a bearer
+//! token, so [`routes::router`]'s handler sits outside
+//! `crate::ratelimit`'s tenant-claim-keyed middleware entirely -- there is
+//! no token to pull a `tenant_id` claim from, only whatever the event
+//! payload itself declares. [`bucket::TelemetryBucket`] enforces a
+//! separate per-(tenant, endpoint) token bucket in front of the insert
+//! instead, since a compromised or just misconfigured agent can otherwise
+//! flood storage at wire speed. Certificate pinning and revocation
+//! enforcement (see [`mtls`]) check the agent's certificate against its
+//! per-endpoint enrollment instead.
+
+mod bucket;
+mod mtls;
+mod routes;
+mod store;
+
+pub use bucket::TelemetryBucket;
+pub use mtls::PresentedCert;
+pub use routes::router;
+pub use store::{bulk_insert, TelemetryEvent};
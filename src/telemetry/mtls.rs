@@ -0,0 +1,207 @@
+//! Client-certificate pinning and revocation for the telemetry ingest
+//! path.
+//!
+//! [`super`]'s module doc already flags that this listener terminates
+//! plain TCP, not TLS, so there's no in-process handshake to hang a
+//! `rustls` `ClientCertVerifier` off yet. What's real here is the
+//! binding a deployment that terminates mTLS at a fronting proxy (nginx,
+//! an ALB, Envoy) needs once it does: [`PresentedCert`] extracts the
+//! fingerprint/subject the proxy already verified and forwarded as
+//! headers, [`verify`] checks the fingerprint against the one pinned for
+//! that endpoint at enrollment and a per-tenant revocation list, and
+//! [`enroll`]/[`revoke`] manage those two tables. Swapping the header
+//! extraction in [`PresentedCert`] for a real `ClientCertVerifier`
+//! callback later is a one-function change -- the pinning/revocation
+//! logic itself doesn't move.
+
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use serde_json::json;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+
+const FINGERPRINT_HEADER: &str = "x-client-cert-fingerprint";
+const SUBJECT_HEADER: &str = "x-client-cert-subject";
+
+/// The fingerprint/subject of a client certificate a TLS-terminating
+/// proxy has already verified and forwarded for this request.
+#[derive(Debug, Clone)]
+pub struct PresentedCert {
+    pub fingerprint: String,
+    pub subject: String,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for PresentedCert
+where
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self> {
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        let fingerprint = header(FINGERPRINT_HEADER)
+            .ok_or_else(|| Error::Unauthorized("no client certificate presented".to_string()))?;
+        let subject = header(SUBJECT_HEADER).unwrap_or_default();
+
+        Ok(PresentedCert { fingerprint, subject })
+    }
+}
+
+/// Pins `cert`'s fingerprint to `endpoint_id` for `tenant_id`, replacing
+/// whatever was previously pinned there. Re-enrolling an already-pinned
+/// endpoint (e.g. after a certificate rotation) overwrites rather than
+/// errors -- rotation is routine, not exceptional.
+pub async fn enroll(db: &TenantScopedPool, tenant_id: Uuid, endpoint_id: Uuid, cert: &PresentedCert) -> Result<()> {
+    let cert = cert.clone();
+    db.with_tenant(tenant_id, |tx| Box::pin(async move {
+        sqlx::query!(
+            r#"
+            INSERT INTO endpoint_cert_enrollments (id, tenant_id, endpoint_id, fingerprint, subject)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (tenant_id, endpoint_id)
+            DO UPDATE SET fingerprint = EXCLUDED.fingerprint, subject = EXCLUDED.subject, enrolled_at = NOW()
+            "#,
+            Uuid::new_v4(),
+            tenant_id,
+            endpoint_id,
+            cert.fingerprint,
+            cert.subject,
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        record_event(
+            tx,
+            tenant_id,
+            endpoint_id,
+            "mtls_enrollment",
+            &cert.fingerprint,
+            json!({ "subject": cert.subject }),
+        )
+        .await
+    }))
+    .await
+}
+
+/// Revokes `fingerprint` for `tenant_id`, so [`verify`] rejects it for
+/// every endpoint it's pinned to from this point on. The audit event is
+/// recorded against whichever endpoint it was last enrolled for, if any
+/// -- a fingerprint revoked before (or without ever) being enrolled has
+/// nothing to attribute the event to, so none is written.
+pub async fn revoke(db: &TenantScopedPool, tenant_id: Uuid, fingerprint: String, reason: String) -> Result<()> {
+    db.with_tenant(tenant_id, |tx| Box::pin(async move {
+        sqlx::query!(
+            r#"
+            INSERT INTO endpoint_cert_revocations (id, tenant_id, fingerprint, reason)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tenant_id, fingerprint) DO UPDATE SET reason = EXCLUDED.reason, revoked_at = NOW()
+            "#,
+            Uuid::new_v4(),
+            tenant_id,
+            fingerprint,
+            reason,
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let enrolled_endpoint = sqlx::query!(
+            "SELECT endpoint_id FROM endpoint_cert_enrollments WHERE tenant_id = $1 AND fingerprint = $2",
+            tenant_id,
+            fingerprint,
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        if let Some(row) = enrolled_endpoint {
+            record_event(
+                tx,
+                tenant_id,
+                row.endpoint_id,
+                "mtls_revocation",
+                &fingerprint,
+                json!({ "reason": reason }),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }))
+    .await
+}
+
+/// Rejects unless `cert` is the fingerprint pinned to `endpoint_id` at
+/// enrollment and that fingerprint hasn't since been revoked for this
+/// tenant.
+pub async fn verify(db: &TenantScopedPool, tenant_id: Uuid, endpoint_id: Uuid, cert: &PresentedCert) -> Result<()> {
+    let fingerprint = cert.fingerprint.clone();
+    db.with_tenant(tenant_id, |tx| Box::pin(async move {
+        let pinned = sqlx::query!(
+            "SELECT fingerprint FROM endpoint_cert_enrollments WHERE tenant_id = $1 AND endpoint_id = $2",
+            tenant_id,
+            endpoint_id,
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| Error::Forbidden(format!("endpoint {endpoint_id} has no enrolled certificate")))?;
+
+        if pinned.fingerprint != fingerprint {
+            return Err(Error::Forbidden(
+                "certificate does not match the certificate pinned for this endpoint".to_string(),
+            ));
+        }
+
+        let revoked = sqlx::query!(
+            "SELECT 1 AS present FROM endpoint_cert_revocations WHERE tenant_id = $1 AND fingerprint = $2",
+            tenant_id,
+            fingerprint,
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .is_some();
+
+        if revoked {
+            return Err(Error::Forbidden("certificate has been revoked".to_string()));
+        }
+
+        Ok(())
+    }))
+    .await
+}
+
+async fn record_event(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    endpoint_id: Uuid,
+    event_type: &str,
+    fingerprint: &str,
+    event_data: serde_json::Value,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO endpoint_events
+            (id, tenant_id, endpoint_id, event_type, process_name, process_pid, event_data, severity, cert_fingerprint)
+        VALUES ($1, $2, $3, $4, NULL, NULL, $5, 0, $6)
+        "#,
+        Uuid::new_v4(),
+        tenant_id,
+        endpoint_id,
+        event_type,
+        event_data,
+        fingerprint,
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
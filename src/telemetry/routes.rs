@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::mtls::{self, PresentedCert};
+use super::store::{self, TelemetryEvent};
+use crate::auth::AuthenticatedUser;
+use crate::error::{Error, Result};
+use crate::metrics::{Metrics, TelemetryOutcome};
+use crate::ratelimit::Decision;
+use crate::state::AppState;
+use crate::webhooks::AlertPayload;
+
+#[derive(Debug, Deserialize)]
+struct IngestRequest {
+    events: Vec<TelemetryEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RevokeCertRequest {
+    fingerprint: String,
+    reason: String,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/telemetry/events", post(ingest_handler))
+        .route(
+            "/api/v1/telemetry/endpoints/:endpoint_id/cert",
+            post(enroll_cert_handler),
+        )
+        .route("/api/v1/telemetry/certs/revoke", post(revoke_cert_handler))
+}
+
+/// Accepts a batch of telemetry events from an agent. Every event in a
+/// batch must declare the same `tenant_id` (a batch spanning tenants
+/// would otherwise need its own ingestion quota and RLS scope per event,
+/// which isn't worth the complexity for what's meant to be one agent's
+/// report of its own activity). The batch is charged against its
+/// tenant's per-endpoint token bucket -- one token per event -- before
+/// anything is written; a batch that would overdraw the bucket is
+/// rejected whole rather than partially stored.
+///
+/// `cert` is checked against every endpoint named in the batch before
+/// anything else happens: a batch whose agent presents a certificate
+/// not pinned (or since revoked) for one of its endpoints is rejected
+/// whole, the same as a batch spanning tenants (see [`mtls::verify`]).
+async fn ingest_handler(
+    State(state): State<AppState>,
+    cert: PresentedCert,
+    Json(request): Json<IngestRequest>,
+) -> Result<StatusCode> {
+    let Some(first) = request.events.first() else {
+        return Ok(StatusCode::ACCEPTED);
+    };
+    let tenant_id = first.tenant_id;
+
+    if request.events.iter().any(|e| e.tenant_id != tenant_id) {
+        return Err(Error::BadRequest(
+            "a telemetry batch cannot span tenants".to_string(),
+        ));
+    }
+
+    let mut endpoint_ids: Vec<Uuid> = request.events.iter().map(|e| e.endpoint_id).collect();
+    endpoint_ids.sort();
+    endpoint_ids.dedup();
+    for endpoint_id in endpoint_ids {
+        mtls::verify(&state.db, tenant_id, endpoint_id, &cert).await?;
+    }
+
+    let metrics = Metrics::global();
+    for event in &request.events {
+        metrics.record_telemetry_event(tenant_id, &event.event_type, TelemetryOutcome::Ingested);
+    }
+
+    let mut events_per_endpoint: HashMap<Uuid, Vec<&TelemetryEvent>> = HashMap::new();
+    for event in &request.events {
+        events_per_endpoint.entry(event.endpoint_id).or_default().push(event);
+    }
+
+    let limits = crate::ratelimit::load(&state.db, tenant_id).await?;
+    for (endpoint_id, events) in &events_per_endpoint {
+        let decision = state
+            .telemetry_bucket
+            .check_and_consume(
+                tenant_id,
+                *endpoint_id,
+                limits.telemetry_events_per_second,
+                limits.telemetry_burst,
+                events.len() as f64,
+            )
+            .await?;
+
+        if let Decision::Limited { retry_after_secs } = decision {
+            for event in events {
+                metrics.record_telemetry_event(tenant_id, &event.event_type, TelemetryOutcome::Dropped);
+            }
+            return Err(Error::RateLimited { retry_after_secs });
+        }
+    }
+
+    store::insert(&state.db, tenant_id, &request.events, Some(&cert.fingerprint)).await?;
+
+    for event in &request.events {
+        metrics.record_telemetry_event(tenant_id, &event.event_type, TelemetryOutcome::Stored);
+
+        for alert in state.correlation.ingest(Uuid::new_v4(), event) {
+            let _ = state
+                .webhooks
+                .dispatch(
+                    tenant_id,
+                    &AlertPayload {
+                        severity: alert.severity,
+                        endpoint_id: alert.endpoint_id,
+                        detection_rule: alert.rule_name,
+                        correlation_id: alert.correlation_id,
+                        produced_at: chrono::Utc::now(),
+                    },
+                )
+                .await;
+        }
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Pins the presented certificate to `endpoint_id` for the caller's
+/// tenant. Called out-of-band (over a regular bearer-authenticated
+/// session, not the mTLS ingest path) at agent deployment time, so
+/// enrolling a certificate doesn't itself require already holding one.
+async fn enroll_cert_handler(
+    user: AuthenticatedUser,
+    Path(endpoint_id): Path<Uuid>,
+    State(state): State<AppState>,
+    cert: PresentedCert,
+) -> Result<StatusCode> {
+    user.require_scope("endpoint:write")?;
+    mtls::enroll(&state.db, user.tenant_id, endpoint_id, &cert).await?;
+    Ok(StatusCode::CREATED)
+}
+
+/// Revokes a certificate fingerprint for the caller's tenant, so the
+/// next batch it's presented on is rejected by [`mtls::verify`]
+/// regardless of which endpoint it's pinned to.
+async fn revoke_cert_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Json(body): Json<RevokeCertRequest>,
+) -> Result<StatusCode> {
+    user.require_scope("endpoint:write")?;
+    mtls::revoke(&state.db, user.tenant_id, body.fingerprint, body.reason).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
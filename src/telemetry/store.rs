@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::Result;
+
+/// One raw telemetry event as an agent reports it. `event_data` carries
+/// whatever additional detail the event type needs (command lines,
+/// registry keys, connection endpoints, ...) without a schema migration
+/// for every new field an agent starts sending.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelemetryEvent {
+    pub endpoint_id: Uuid,
+    pub tenant_id: Uuid,
+    pub event_type: String,
+    pub process_name: Option<String>,
+    pub process_pid: Option<i32>,
+    #[serde(default)]
+    pub event_data: serde_json::Value,
+    pub severity: i32,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Inserts `events` for `tenant_id` inside one tenant-scoped transaction.
+/// Callers are expected to have already confirmed every event in the
+/// batch carries this same `tenant_id` (see [`super::routes`]).
+/// `cert_fingerprint` is the client certificate that authenticated the
+/// batch (see [`super::mtls`]), recorded on every row so a later
+/// `query_endpoint_events` audit can tell which certificate produced it;
+/// it's `None` for paths with no mTLS context of their own, like
+/// `bin/bulk_loader.rs`'s offline replay.
+pub async fn insert(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    events: &[TelemetryEvent],
+    cert_fingerprint: Option<&str>,
+) -> Result<()> {
+    let events = events.to_vec();
+    let cert_fingerprint = cert_fingerprint.map(str::to_string);
+
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(async move {
+            for event in &events {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO endpoint_events
+                        (id, tenant_id, endpoint_id, event_type, process_name, process_pid, event_data, severity, time, cert_fingerprint)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                    "#,
+                    Uuid::new_v4(),
+                    tenant_id,
+                    event.endpoint_id,
+                    event.event_type,
+                    event.process_name,
+                    event.process_pid,
+                    event.event_data,
+                    event.severity,
+                    event.timestamp,
+                    cert_fingerprint,
+                )
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+/// Inserts `events` for `tenant_id` as a single multi-row `INSERT`
+/// rather than one round trip per row, for callers with far larger
+/// batches than the live ingest path ever sees (see
+/// `bin/bulk_loader.rs`). Like [`insert`], every event in the batch must
+/// already carry this same `tenant_id`; the caller is expected to have
+/// grouped by tenant first; so an unknown tenant_id surfaces as the
+/// `tenant_id` foreign key violation on the whole batch rather than a
+/// silently dropped row.
+pub async fn bulk_insert(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    events: &[TelemetryEvent],
+) -> Result<u64> {
+    if events.is_empty() {
+        return Ok(0);
+    }
+
+    let mut builder = sqlx::QueryBuilder::new(
+        "INSERT INTO endpoint_events \
+         (id, tenant_id, endpoint_id, event_type, process_name, process_pid, event_data, severity, time) ",
+    );
+    builder.push_values(events, |mut row, event| {
+        row.push_bind(Uuid::new_v4())
+            .push_bind(tenant_id)
+            .push_bind(event.endpoint_id)
+            .push_bind(event.event_type.clone())
+            .push_bind(event.process_name.clone())
+            .push_bind(event.process_pid)
+            .push_bind(event.event_data.clone())
+            .push_bind(event.severity)
+            .push_bind(event.timestamp);
+    });
+
+    let result = builder.build().execute(&mut **tx).await?;
+    Ok(result.rows_affected())
+}
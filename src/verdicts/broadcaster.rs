@@ -0,0 +1,119 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+const CHANNEL_CAPACITY: usize = 256;
+const REPLAY_BUFFER_CAPACITY: usize = 256;
+
+/// A verdict as pushed to subscribers of a tenant's stream.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerdictEvent {
+    pub artifact_id: Uuid,
+    pub verdict: String,
+    pub score: f64,
+}
+
+/// A [`VerdictEvent`] tagged with its per-tenant sequence id, so an SSE
+/// client that reconnects with a `Last-Event-ID` header can ask for
+/// everything it missed instead of silently dropping events across a
+/// flaky connection. `published_at` is never serialized to a subscriber;
+/// it only exists so the delivery path can record how long the event sat
+/// before it was pushed (see `crate::metrics`).
+#[derive(Debug, Clone)]
+pub struct SequencedEvent {
+    pub id: u64,
+    pub event: VerdictEvent,
+    pub published_at: Instant,
+}
+
+#[derive(Default)]
+struct TenantChannel {
+    sender: Option<broadcast::Sender<SequencedEvent>>,
+    replay_buffer: VecDeque<SequencedEvent>,
+    next_id: u64,
+}
+
+impl TenantChannel {
+    fn sender(&mut self) -> broadcast::Sender<SequencedEvent> {
+        self.sender
+            .get_or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .clone()
+    }
+}
+
+/// One broadcast channel per tenant, each paired with a small replay
+/// buffer of recently-published events. Subscribing never crosses
+/// tenants: [`subscribe`](Self::subscribe) and
+/// [`subscribe_with_replay`](Self::subscribe_with_replay) only ever hand
+/// out a receiver for the tenant_id they were asked for, and
+/// [`publish`](Self::publish) only reaches receivers on that same
+/// tenant's channel.
+#[derive(Clone, Default)]
+pub struct VerdictBroadcaster {
+    channels: Arc<Mutex<HashMap<Uuid, TenantChannel>>>,
+}
+
+impl VerdictBroadcaster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publishes `event` to every current subscriber of `tenant_id` and
+    /// appends it to that tenant's replay buffer. A tenant with no
+    /// subscribers yet still gets the event recorded for replay, but the
+    /// broadcast send itself is simply dropped, the same as a broadcast
+    /// channel with no receivers.
+    pub fn publish(&self, tenant_id: Uuid, event: VerdictEvent) {
+        let mut channels = self.channels.lock().unwrap();
+        let channel = channels.entry(tenant_id).or_default();
+
+        let id = channel.next_id;
+        channel.next_id += 1;
+        let sequenced = SequencedEvent {
+            id,
+            event,
+            published_at: Instant::now(),
+        };
+
+        channel.replay_buffer.push_back(sequenced.clone());
+        if channel.replay_buffer.len() > REPLAY_BUFFER_CAPACITY {
+            channel.replay_buffer.pop_front();
+        }
+
+        let _ = channel.sender().send(sequenced);
+    }
+
+    pub fn subscribe(&self, tenant_id: Uuid) -> broadcast::Receiver<SequencedEvent> {
+        self.subscribe_with_replay(tenant_id, None).1
+    }
+
+    /// Subscribes to `tenant_id`'s stream and, in the same locked step (so
+    /// nothing published in between is missed or duplicated), returns
+    /// every buffered event after `last_event_id` for the caller to replay
+    /// before switching over to the live receiver. `last_event_id: None`
+    /// replays nothing, matching a client connecting for the first time.
+    pub fn subscribe_with_replay(
+        &self,
+        tenant_id: Uuid,
+        last_event_id: Option<u64>,
+    ) -> (Vec<SequencedEvent>, broadcast::Receiver<SequencedEvent>) {
+        let mut channels = self.channels.lock().unwrap();
+        let channel = channels.entry(tenant_id).or_default();
+
+        let replay = match last_event_id {
+            Some(last_id) => channel
+                .replay_buffer
+                .iter()
+                .filter(|e| e.id > last_id)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        (replay, channel.sender().subscribe())
+    }
+}
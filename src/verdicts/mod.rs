@@ -0,0 +1,10 @@
+//! Pushes verdicts to clients as the analysis pipeline produces them,
+//! instead of making clients poll. [`VerdictBroadcaster`] keeps one
+//! broadcast channel per tenant, so a subscriber can only ever receive
+//! events for the tenant_id it authenticated as.
+
+mod broadcaster;
+mod routes;
+
+pub use broadcaster::{VerdictBroadcaster, VerdictEvent};
+pub use routes::router;
@@ -0,0 +1,133 @@
+use std::convert::Infallible;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use futures_util::{stream, Stream, StreamExt};
+use serde::Deserialize;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use uuid::Uuid;
+
+use super::broadcaster::SequencedEvent;
+use crate::auth::AuthenticatedUser;
+use crate::error::Result;
+use crate::metrics::Metrics;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct StreamParams {
+    pub artifact_id: Option<Uuid>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/verdicts/stream", get(stream_verdicts_sse))
+        .route("/api/v1/verdicts/ws", get(stream_verdicts_ws))
+}
+
+/// Streams verdicts over SSE, the lighter-weight option for clients that
+/// only ever consume the one-way feed `stream_verdicts_ws` also serves. A
+/// reconnecting client that sends a `Last-Event-ID` header gets every
+/// buffered event published since that sequence id replayed first, so a
+/// flaky connection doesn't silently drop verdicts.
+async fn stream_verdicts_sse(
+    user: AuthenticatedUser,
+    Query(params): Query<StreamParams>,
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>> {
+    user.require_scope("verdict:read")?;
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+
+    let (replay, rx) = state
+        .verdicts
+        .subscribe_with_replay(user.tenant_id, last_event_id);
+    let artifact_filter = params.artifact_id;
+
+    let replay_stream = stream::iter(replay.into_iter().filter_map(move |sequenced| {
+        matching_sse_event(sequenced, artifact_filter)
+    }));
+
+    let live_stream = BroadcastStream::new(rx).filter_map(move |message| {
+        let event = matching_event(message, artifact_filter).map(|sequenced| to_sse_event(&sequenced));
+        async move { event }
+    });
+
+    Ok(Sse::new(replay_stream.chain(live_stream)).keep_alive(KeepAlive::default()))
+}
+
+async fn stream_verdicts_ws(
+    user: AuthenticatedUser,
+    Query(params): Query<StreamParams>,
+    State(state): State<AppState>,
+    ws: WebSocketUpgrade,
+) -> Result<impl IntoResponse> {
+    user.require_scope("verdict:read")?;
+
+    let rx = state.verdicts.subscribe(user.tenant_id);
+    let artifact_filter = params.artifact_id;
+
+    Ok(ws.on_upgrade(move |socket| forward_verdicts(socket, rx, artifact_filter)))
+}
+
+async fn forward_verdicts(
+    mut socket: WebSocket,
+    rx: broadcast::Receiver<SequencedEvent>,
+    artifact_filter: Option<Uuid>,
+) {
+    let mut stream = BroadcastStream::new(rx);
+    while let Some(message) = stream.next().await {
+        let Some(sequenced) = matching_event(message, artifact_filter) else {
+            continue;
+        };
+        let Ok(payload) = serde_json::to_string(&sequenced.event) else {
+            continue;
+        };
+        if socket.send(Message::Text(payload)).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Drops lagged-subscriber errors and anything that doesn't match the
+/// `?artifact_id=` filter. Every event that survives the filter is about
+/// to be pushed to a live subscriber, so this is also where delivery
+/// latency is recorded against `published_at`.
+fn matching_event(
+    message: std::result::Result<SequencedEvent, BroadcastStreamRecvError>,
+    artifact_filter: Option<Uuid>,
+) -> Option<SequencedEvent> {
+    let sequenced = message.ok()?;
+    if artifact_filter.is_some_and(|id| id != sequenced.event.artifact_id) {
+        return None;
+    }
+    Metrics::global().observe_verdict_delivery_latency(sequenced.published_at.elapsed().as_secs_f64());
+    Some(sequenced)
+}
+
+fn matching_sse_event(
+    sequenced: SequencedEvent,
+    artifact_filter: Option<Uuid>,
+) -> Option<std::result::Result<Event, Infallible>> {
+    if artifact_filter.is_some_and(|id| id != sequenced.event.artifact_id) {
+        return None;
+    }
+    Some(to_sse_event(&sequenced))
+}
+
+fn to_sse_event(sequenced: &SequencedEvent) -> std::result::Result<Event, Infallible> {
+    Ok(Event::default()
+        .id(sequenced.id.to_string())
+        .json_data(&sequenced.event)
+        .expect("VerdictEvent always serializes to JSON"))
+}
@@ -0,0 +1,238 @@
+//! Per-webhook circuit breaker. [`allow`] is consulted before a new
+//! verdict is handed to [`super::delivery::deliver_with_retry`]: once
+//! enough consecutive deliveries have exhausted their retry budget, it
+//! starts answering [`Admission::Blocked`] so new verdicts route straight
+//! to [`super::failures`] instead of burning another full retry budget
+//! against an endpoint that's already down. After a cooldown it admits
+//! exactly one probe delivery through; that probe closes the breaker on
+//! success or reopens it on failure.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+
+/// Consecutive exhausted deliveries -- not individual HTTP attempts --
+/// before the breaker opens.
+const FAILURE_THRESHOLD: i32 = 3;
+const COOLDOWN: Duration = Duration::minutes(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl State {
+    fn as_str(self) -> &'static str {
+        match self {
+            State::Closed => "closed",
+            State::Open => "open",
+            State::HalfOpen => "half_open",
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "closed" => Ok(State::Closed),
+            "open" => Ok(State::Open),
+            "half_open" => Ok(State::HalfOpen),
+            other => Err(Error::Internal(anyhow::anyhow!("unknown breaker state: {other}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_str_and_parse_round_trip() {
+        for state in [State::Closed, State::Open, State::HalfOpen] {
+            assert_eq!(State::parse(state.as_str()).unwrap(), state);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_unknown_state() {
+        assert!(State::parse("half-open-typo").is_err());
+    }
+}
+
+/// What an operator sees for a webhook's breaker via the status endpoint.
+#[derive(Debug, Serialize)]
+pub struct BreakerStatus {
+    pub state: String,
+    pub consecutive_failures: i32,
+    pub next_probe_at: Option<DateTime<Utc>>,
+}
+
+/// What [`allow`] tells the dispatcher to do with a newly-arrived verdict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Admission {
+    /// Closed, or this call just won the half-open probe slot -- attempt
+    /// delivery normally.
+    Allow,
+    /// Open and no probe due yet -- skip delivery, dead-letter instead.
+    Blocked,
+}
+
+/// Decides whether a new verdict for `webhook_id` should be delivered or
+/// routed straight to the dead-letter queue. Rows are created lazily on
+/// first failure, so a webhook that's never failed has no row and is
+/// always [`Admission::Allow`].
+pub(super) async fn allow(db: &TenantScopedPool, tenant_id: Uuid, webhook_id: Uuid) -> Result<Admission> {
+    db.with_tenant(tenant_id, |tx| Box::pin(check_and_probe(tx, tenant_id, webhook_id))).await
+}
+
+async fn check_and_probe(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid, webhook_id: Uuid) -> Result<Admission> {
+    let row = sqlx::query!(
+        "SELECT state, next_probe_at FROM webhook_circuit_breakers WHERE tenant_id = $1 AND webhook_id = $2",
+        tenant_id,
+        webhook_id,
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(Admission::Allow);
+    };
+
+    match State::parse(&row.state)? {
+        State::Closed => Ok(Admission::Allow),
+        State::HalfOpen => Ok(Admission::Blocked),
+        State::Open => {
+            let due = row.next_probe_at.is_some_and(|next| Utc::now() >= next);
+            if !due {
+                return Ok(Admission::Blocked);
+            }
+
+            // Claim the probe slot: only the caller that actually flips
+            // open -> half_open gets to deliver, so a burst of verdicts
+            // arriving once the cooldown expires doesn't all treat
+            // themselves as the probe.
+            let claimed = sqlx::query!(
+                r#"
+                UPDATE webhook_circuit_breakers
+                SET state = 'half_open', updated_at = NOW()
+                WHERE tenant_id = $1 AND webhook_id = $2 AND state = 'open'
+                "#,
+                tenant_id,
+                webhook_id,
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            Ok(if claimed.rows_affected() == 1 { Admission::Allow } else { Admission::Blocked })
+        }
+    }
+}
+
+/// Resets `webhook_id`'s breaker to closed -- what
+/// [`super::delivery::WebhookDispatcher::recover`] does before re-driving
+/// dead-lettered deliveries, since an operator recovering manually is
+/// already vouching that the receiver is back.
+pub(super) async fn reset(db: &TenantScopedPool, tenant_id: Uuid, webhook_id: Uuid) -> Result<()> {
+    db.with_tenant(tenant_id, |tx| Box::pin(reset_to_closed(tx, tenant_id, webhook_id))).await
+}
+
+async fn reset_to_closed(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid, webhook_id: Uuid) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO webhook_circuit_breakers (tenant_id, webhook_id, state, consecutive_failures, opened_at, next_probe_at)
+        VALUES ($1, $2, 'closed', 0, NULL, NULL)
+        ON CONFLICT (tenant_id, webhook_id) DO UPDATE
+        SET state = 'closed', consecutive_failures = 0, opened_at = NULL, next_probe_at = NULL, updated_at = NOW()
+        "#,
+        tenant_id,
+        webhook_id,
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Records a delivery that made it through -- closes the breaker (a no-op
+/// if it was already closed).
+pub(super) async fn record_success(db: &TenantScopedPool, tenant_id: Uuid, webhook_id: Uuid) -> Result<()> {
+    reset(db, tenant_id, webhook_id).await
+}
+
+/// Records a delivery that exhausted its retries. A webhook already
+/// half-open (i.e. this delivery was the probe) reopens immediately
+/// regardless of the threshold; a closed webhook only opens once
+/// [`FAILURE_THRESHOLD`] consecutive failures have piled up.
+pub(super) async fn record_failure(db: &TenantScopedPool, tenant_id: Uuid, webhook_id: Uuid) -> Result<()> {
+    db.with_tenant(tenant_id, |tx| Box::pin(bump_failure(tx, tenant_id, webhook_id))).await
+}
+
+async fn bump_failure(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid, webhook_id: Uuid) -> Result<()> {
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO webhook_circuit_breakers (tenant_id, webhook_id, state, consecutive_failures)
+        VALUES ($1, $2, 'closed', 1)
+        ON CONFLICT (tenant_id, webhook_id) DO UPDATE
+        SET consecutive_failures = webhook_circuit_breakers.consecutive_failures + 1, updated_at = NOW()
+        RETURNING state, consecutive_failures
+        "#,
+        tenant_id,
+        webhook_id,
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let state = State::parse(&row.state)?;
+    let should_open = state == State::HalfOpen || row.consecutive_failures >= FAILURE_THRESHOLD;
+    if !should_open {
+        return Ok(());
+    }
+
+    let next_probe_at = Utc::now() + COOLDOWN;
+    sqlx::query!(
+        r#"
+        UPDATE webhook_circuit_breakers
+        SET state = 'open', opened_at = NOW(), next_probe_at = $3, updated_at = NOW()
+        WHERE tenant_id = $1 AND webhook_id = $2
+        "#,
+        tenant_id,
+        webhook_id,
+        next_probe_at,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// The breaker status for a single webhook -- `closed` with zero failures
+/// if nothing's ever failed, since rows are created lazily.
+pub(super) async fn status(db: &TenantScopedPool, tenant_id: Uuid, webhook_id: Uuid) -> Result<BreakerStatus> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_status(tx, tenant_id, webhook_id))).await
+}
+
+async fn fetch_status(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid, webhook_id: Uuid) -> Result<BreakerStatus> {
+    let row = sqlx::query!(
+        "SELECT state, consecutive_failures, next_probe_at FROM webhook_circuit_breakers WHERE tenant_id = $1 AND webhook_id = $2",
+        tenant_id,
+        webhook_id,
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(match row {
+        Some(row) => BreakerStatus {
+            state: row.state,
+            consecutive_failures: row.consecutive_failures,
+            next_probe_at: row.next_probe_at,
+        },
+        None => BreakerStatus {
+            state: State::Closed.as_str().to_string(),
+            consecutive_failures: 0,
+            next_probe_at: None,
+        },
+    })
+}
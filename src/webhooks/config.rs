@@ -0,0 +1,239 @@
+//! CRUD for a tenant's registered webhook callback URLs, backed by
+//! `tenant_webhooks`.
+
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+
+/// How long a rotated-out secret still signs deliveries (as
+/// [`super::delivery::WebhookDispatcher::dispatch`]'s second `v1=`
+/// value) before [`enabled_targets`] stops returning it. Long enough for
+/// an operator to roll the new secret out to their receiver without a
+/// gap where neither secret on file there verifies.
+const ROTATION_GRACE: Duration = Duration::hours(24);
+
+/// A registered webhook. `secret` is only ever returned from
+/// [`register`], at creation time -- callers that need it again to
+/// re-verify a signature have to keep their own copy, the same as any
+/// other webhook provider's "shown once" secret.
+#[derive(Debug, Serialize)]
+pub struct WebhookRegistration {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: Option<String>,
+    pub enabled: bool,
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub async fn register(db: &TenantScopedPool, tenant_id: Uuid, url: String) -> Result<WebhookRegistration> {
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(Error::BadRequest("webhook url must be http(s)".to_string()));
+    }
+
+    let id = Uuid::new_v4();
+    let secret = generate_secret();
+
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(insert(tx, id, tenant_id, url.clone(), secret.clone()))
+    })
+    .await?;
+
+    Ok(WebhookRegistration {
+        id,
+        url,
+        secret: Some(secret),
+        enabled: true,
+    })
+}
+
+/// Replaces a webhook's signing secret, keeping the old one valid as
+/// `secret_previous` for [`ROTATION_GRACE`] rather than invalidating it
+/// immediately -- see [`super::signing`] for how both sign a delivery
+/// during that window.
+pub async fn rotate(db: &TenantScopedPool, tenant_id: Uuid, webhook_id: Uuid) -> Result<WebhookRegistration> {
+    let secret = generate_secret();
+
+    let url = db
+        .with_tenant(tenant_id, |tx| Box::pin(rotate_secret(tx, webhook_id, secret.clone())))
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    Ok(WebhookRegistration {
+        id: webhook_id,
+        url,
+        secret: Some(secret),
+        enabled: true,
+    })
+}
+
+async fn rotate_secret(
+    tx: &mut Transaction<'_, Postgres>,
+    webhook_id: Uuid,
+    new_secret: String,
+) -> Result<Option<String>> {
+    let previous_expires_at = Utc::now() + ROTATION_GRACE;
+
+    let row = sqlx::query!(
+        r#"
+        UPDATE tenant_webhooks
+        SET secret_previous = secret,
+            secret_previous_expires_at = $2,
+            secret = $3
+        WHERE id = $1
+        RETURNING url
+        "#,
+        webhook_id,
+        previous_expires_at,
+        new_secret,
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(row.map(|row| row.url))
+}
+
+async fn insert(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    tenant_id: Uuid,
+    url: String,
+    secret: String,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO tenant_webhooks (id, tenant_id, url, secret) VALUES ($1, $2, $3, $4)",
+        id,
+        tenant_id,
+        url,
+        secret,
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn list(db: &TenantScopedPool, tenant_id: Uuid) -> Result<Vec<WebhookRegistration>> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_all(tx, tenant_id))).await
+}
+
+async fn fetch_all(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid) -> Result<Vec<WebhookRegistration>> {
+    let rows = sqlx::query!(
+        "SELECT id, url, enabled FROM tenant_webhooks WHERE tenant_id = $1",
+        tenant_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| WebhookRegistration {
+            id: row.id,
+            url: row.url,
+            secret: None,
+            enabled: row.enabled,
+        })
+        .collect())
+}
+
+/// Deleting a webhook that doesn't exist (or already belongs to nothing,
+/// because RLS hides cross-tenant rows) is not an error.
+pub async fn delete(db: &TenantScopedPool, tenant_id: Uuid, webhook_id: Uuid) -> Result<()> {
+    db.with_tenant(tenant_id, |tx| Box::pin(remove(tx, webhook_id))).await
+}
+
+async fn remove(tx: &mut Transaction<'_, Postgres>, webhook_id: Uuid) -> Result<()> {
+    sqlx::query!("DELETE FROM tenant_webhooks WHERE id = $1", webhook_id)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// The rows [`delivery::WebhookDispatcher`] needs to fan a dispatch out
+/// to every enabled webhook for a tenant, secrets included. `previous_secret`
+/// is `Some` only while a rotation is still inside its grace window (see
+/// [`rotate`]); every delivery is signed with whichever of the two are set.
+pub(super) struct WebhookTarget {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub previous_secret: Option<String>,
+}
+
+pub(super) async fn enabled_targets(db: &TenantScopedPool, tenant_id: Uuid) -> Result<Vec<WebhookTarget>> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_enabled(tx, tenant_id))).await
+}
+
+async fn fetch_enabled(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid) -> Result<Vec<WebhookTarget>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, url, secret, secret_previous, secret_previous_expires_at
+        FROM tenant_webhooks
+        WHERE tenant_id = $1 AND enabled
+        "#,
+        tenant_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let now = Utc::now();
+    Ok(rows.into_iter().map(|row| row_to_target(row.id, row.url, row.secret, row.secret_previous, row.secret_previous_expires_at, now)).collect())
+}
+
+/// The single-target counterpart of [`enabled_targets`], used by
+/// [`super::delivery::WebhookDispatcher::recover`] to re-resolve a
+/// dead-lettered delivery's webhook -- `None` if it's since been deleted
+/// or disabled, in which case the caller drops the redelivery.
+pub(super) async fn target_by_id(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    webhook_id: Uuid,
+) -> Result<Option<WebhookTarget>> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_one_enabled(tx, webhook_id)))
+        .await
+}
+
+async fn fetch_one_enabled(tx: &mut Transaction<'_, Postgres>, webhook_id: Uuid) -> Result<Option<WebhookTarget>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id, url, secret, secret_previous, secret_previous_expires_at
+        FROM tenant_webhooks
+        WHERE id = $1 AND enabled
+        "#,
+        webhook_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(row.map(|row| {
+        row_to_target(row.id, row.url, row.secret, row.secret_previous, row.secret_previous_expires_at, Utc::now())
+    }))
+}
+
+fn row_to_target(
+    id: Uuid,
+    url: String,
+    secret: String,
+    secret_previous: Option<String>,
+    secret_previous_expires_at: Option<chrono::DateTime<Utc>>,
+    now: chrono::DateTime<Utc>,
+) -> WebhookTarget {
+    let previous_secret = match secret_previous_expires_at {
+        Some(expires_at) if expires_at > now => secret_previous,
+        _ => None,
+    };
+    WebhookTarget {
+        id,
+        url,
+        secret,
+        previous_secret,
+    }
+}
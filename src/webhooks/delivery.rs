@@ -0,0 +1,217 @@
+//! Signs and delivers alert payloads to a tenant's registered webhooks,
+//! retrying non-2xx responses with exponential backoff and dead-lettering
+//! into [`super::failures`] once retries are exhausted. Every target is
+//! first run past [`breaker`] so a persistently broken receiver gets
+//! dead-lettered without spending a retry budget, then past a
+//! [`PolicyEngine`] so a severity floor, rate limit, or dedup rule can
+//! keep a noisy verdict from ever reaching `deliver_with_retry`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::breaker::{self, Admission};
+use super::config::{self, WebhookTarget};
+use super::lifecycle::Queued;
+use super::{failures, signing};
+use crate::db::TenantScopedPool;
+use crate::error::Result;
+use crate::policy::{DeliveryDecision, Integration, PolicyEngine, VerdictContext};
+
+const MAX_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// The shape a future detection/correlation engine would hand to
+/// [`WebhookDispatcher::dispatch`] -- mirrors the fields the requested
+/// `query_alerts` response carries (severity, endpoint_id,
+/// detection_rule, correlation_id), since nothing in this tree generates
+/// those yet. `produced_at` is when the verdict behind the alert was
+/// produced, not when delivery was attempted -- it's what
+/// [`failures::recover`]'s `since` filters against.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertPayload {
+    pub severity: i32,
+    pub endpoint_id: Uuid,
+    pub detection_rule: String,
+    pub correlation_id: Uuid,
+    pub produced_at: DateTime<Utc>,
+}
+
+/// Fans an [`AlertPayload`] out to every webhook a tenant has registered.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    db: TenantScopedPool,
+    http: reqwest::Client,
+    policy: Arc<dyn PolicyEngine>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(db: TenantScopedPool, policy: Arc<dyn PolicyEngine>) -> Self {
+        Self {
+            db,
+            http: reqwest::Client::new(),
+            policy,
+        }
+    }
+
+    /// Looks up `tenant_id`'s enabled webhooks and, for each one that the
+    /// circuit breaker and policy engine both let through, spawns one
+    /// delivery (with its own retry loop), so a slow or down receiver
+    /// never blocks the others or the caller. `alert.correlation_id`
+    /// stands in for the artifact id the policy layer dedups on -- there's
+    /// no artifact concept on this path yet, just the correlation rule
+    /// that fired.
+    pub async fn dispatch(&self, tenant_id: Uuid, alert: &AlertPayload) -> Result<()> {
+        let targets = config::enabled_targets(&self.db, tenant_id).await?;
+        let body =
+            serde_json::to_vec(alert).map_err(|e| crate::error::Error::Internal(anyhow::anyhow!(e)))?;
+
+        for target in targets {
+            if breaker::allow(&self.db, tenant_id, target.id).await? == Admission::Blocked {
+                tracing::warn!(webhook_id = %target.id, "circuit breaker open, dead-lettering verdict");
+                let _ = failures::record(
+                    &self.db,
+                    tenant_id,
+                    target.id,
+                    Uuid::new_v4(),
+                    &body,
+                    alert.produced_at,
+                    0,
+                    "circuit breaker open".to_string(),
+                )
+                .await;
+                continue;
+            }
+
+            let ctx = VerdictContext {
+                tenant_id,
+                integration: Integration::Webhook,
+                integration_id: target.id,
+                artifact_id: alert.correlation_id,
+                file_hash: None,
+                severity: alert.severity.clamp(0, 10) as u8,
+            };
+
+            match self.policy.evaluate(&ctx).await? {
+                DeliveryDecision::Suppress { reason } => {
+                    tracing::info!(webhook_id = %target.id, reason, "webhook delivery suppressed by policy");
+                }
+                DeliveryDecision::Defer { retry_after, reason } => {
+                    tracing::info!(webhook_id = %target.id, reason, "webhook delivery deferred by policy");
+                    let db = self.db.clone();
+                    let http = self.http.clone();
+                    let body = body.clone();
+                    let produced_at = alert.produced_at;
+                    tokio::spawn(async move {
+                        tokio::time::sleep(retry_after).await;
+                        deliver_with_retry(db, http, tenant_id, target, produced_at, body).await;
+                    });
+                }
+                DeliveryDecision::Send => {
+                    let db = self.db.clone();
+                    let http = self.http.clone();
+                    let body = body.clone();
+                    tokio::spawn(deliver_with_retry(db, http, tenant_id, target, alert.produced_at, body));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-drives every dead-lettered delivery produced at or after `since`
+    /// back through [`deliver_with_retry`] -- see [`failures::recover`]
+    /// for the claiming and age-limit logic. A webhook that's been
+    /// deleted or disabled since its delivery failed is claimed (so it
+    /// stops showing up as outstanding) but not re-sent. An operator
+    /// recovering manually is already vouching that the receiver is back,
+    /// so each recovered webhook's breaker is reset to closed rather than
+    /// waiting out its own cooldown.
+    pub async fn recover(&self, tenant_id: Uuid, since: DateTime<Utc>) -> Result<u64> {
+        let claimed = failures::recover(&self.db, tenant_id, since).await?;
+        let count = claimed.len() as u64;
+
+        for failure in claimed {
+            let _ = breaker::reset(&self.db, tenant_id, failure.webhook_id).await;
+
+            let Some(target) = config::target_by_id(&self.db, tenant_id, failure.webhook_id).await? else {
+                continue;
+            };
+            let Ok(body) = serde_json::to_vec(&failure.alert_payload) else {
+                continue;
+            };
+
+            let db = self.db.clone();
+            let http = self.http.clone();
+            tokio::spawn(deliver_with_retry(db, http, tenant_id, target, failure.produced_at, body));
+        }
+
+        Ok(count)
+    }
+}
+
+async fn deliver_with_retry(
+    db: TenantScopedPool,
+    http: reqwest::Client,
+    tenant_id: Uuid,
+    target: WebhookTarget,
+    produced_at: DateTime<Utc>,
+    body: Vec<u8>,
+) {
+    let ts = Utc::now().timestamp();
+    let mut secrets = vec![target.secret.as_str()];
+    if let Some(previous) = target.previous_secret.as_deref() {
+        secrets.push(previous);
+    }
+    let signature = signing::signature_header(&secrets, ts, &body);
+
+    let mut backoff = INITIAL_BACKOFF;
+    let queued = Queued::new(db.clone(), tenant_id, target.id);
+    let delivery_id = queued.delivery_id();
+    let mut attempt_token = queued.dispatch().await.attempt(1).await;
+
+    loop {
+        let attempt = attempt_token.attempt_number();
+        let result = http
+            .post(&target.url)
+            .header("Content-Type", "application/json")
+            .header("X-Sentinel-Timestamp", ts.to_string())
+            .header("X-Sentinel-Signature", &signature)
+            .body(body.clone())
+            .send()
+            .await;
+
+        let last_error = match result {
+            Ok(response) if response.status().is_success() => {
+                attempt_token.delivered().await;
+                let _ = breaker::record_success(&db, tenant_id, target.id).await;
+                return;
+            }
+            Ok(response) => {
+                tracing::warn!(url = %target.url, status = %response.status(), attempt, "webhook delivery rejected");
+                format!("receiver returned {}", response.status())
+            }
+            Err(error) => {
+                tracing::warn!(url = %target.url, %error, attempt, "webhook delivery failed");
+                error.to_string()
+            }
+        };
+
+        let failed_token = attempt_token.failed(last_error.clone()).await;
+        let attempt = failed_token.attempt_number();
+
+        if attempt == MAX_ATTEMPTS {
+            tracing::error!(url = %target.url, "webhook delivery exhausted retries, dead-lettering");
+            let _ = failures::record(&db, tenant_id, target.id, delivery_id, &body, produced_at, attempt, last_error).await;
+            let _ = breaker::record_failure(&db, tenant_id, target.id).await;
+            return;
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+        attempt_token = failed_token.retry().await;
+    }
+}
@@ -0,0 +1,174 @@
+//! The dead-letter log for webhook deliveries that exhausted
+//! [`super::delivery`]'s retry budget, and the recovery path that
+//! re-drives them once a receiver is back up.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::{Error, Result};
+
+/// How far back a recovery's `since` is allowed to reach -- failures
+/// older than this are treated as purged, so a typo'd or stale `since`
+/// doesn't silently redeliver nothing and look like it worked.
+const MAX_RECOVERY_AGE: Duration = Duration::days(14);
+
+/// One exhausted delivery, listed for an operator to see what a downed
+/// receiver missed.
+#[derive(Debug, Serialize)]
+pub struct FailedDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    /// The id [`super::lifecycle`] recorded this delivery's stage
+    /// transitions under -- `None` for failures logged before the
+    /// lifecycle reporter existed. Follow it to
+    /// `GET .../deliveries/:delivery_id/events` for the attempt-by-attempt
+    /// detail behind `last_error`.
+    pub delivery_id: Option<Uuid>,
+    pub produced_at: DateTime<Utc>,
+    pub failed_at: DateTime<Utc>,
+    pub attempts: i32,
+    pub last_error: String,
+}
+
+pub(super) struct ClaimedFailure {
+    pub webhook_id: Uuid,
+    pub produced_at: DateTime<Utc>,
+    pub alert_payload: serde_json::Value,
+}
+
+/// Records a delivery [`super::delivery::deliver_with_retry`] gave up on.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn record(
+    db: &TenantScopedPool,
+    tenant_id: Uuid,
+    webhook_id: Uuid,
+    delivery_id: Uuid,
+    body: &[u8],
+    produced_at: DateTime<Utc>,
+    attempts: u32,
+    last_error: String,
+) -> Result<()> {
+    let alert_payload: serde_json::Value = serde_json::from_slice(body).unwrap_or(serde_json::Value::Null);
+    let attempts = attempts as i32;
+
+    db.with_tenant(tenant_id, |tx| {
+        Box::pin(insert(
+            tx,
+            tenant_id,
+            webhook_id,
+            delivery_id,
+            alert_payload,
+            produced_at,
+            attempts,
+            last_error,
+        ))
+    })
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn insert(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    webhook_id: Uuid,
+    delivery_id: Uuid,
+    alert_payload: serde_json::Value,
+    produced_at: DateTime<Utc>,
+    attempts: i32,
+    last_error: String,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO webhook_delivery_failures
+            (id, tenant_id, webhook_id, delivery_id, alert_payload, produced_at, attempts, last_error)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+        Uuid::new_v4(),
+        tenant_id,
+        webhook_id,
+        delivery_id,
+        alert_payload,
+        produced_at,
+        attempts,
+        last_error,
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Lists every still-outstanding failure for a tenant, most recent
+/// first -- the read side an operator checks before deciding to recover.
+pub async fn list(db: &TenantScopedPool, tenant_id: Uuid) -> Result<Vec<FailedDelivery>> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_outstanding(tx, tenant_id))).await
+}
+
+async fn fetch_outstanding(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid) -> Result<Vec<FailedDelivery>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, webhook_id, delivery_id, produced_at, failed_at, attempts, last_error
+        FROM webhook_delivery_failures
+        WHERE tenant_id = $1 AND recovered_at IS NULL
+        ORDER BY failed_at DESC
+        "#,
+        tenant_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| FailedDelivery {
+            id: row.id,
+            webhook_id: row.webhook_id,
+            delivery_id: row.delivery_id,
+            produced_at: row.produced_at,
+            failed_at: row.failed_at,
+            attempts: row.attempts,
+            last_error: row.last_error,
+        })
+        .collect())
+}
+
+/// Claims every outstanding failure produced at or after `since` for
+/// redelivery, marking each `recovered_at` so a second recovery call
+/// doesn't double-send it. Rejects a `since` older than
+/// [`MAX_RECOVERY_AGE`] with [`Error::UnprocessableEntity`], since
+/// failures past that age are assumed purged rather than just old.
+pub(super) async fn recover(db: &TenantScopedPool, tenant_id: Uuid, since: DateTime<Utc>) -> Result<Vec<ClaimedFailure>> {
+    if since < Utc::now() - MAX_RECOVERY_AGE {
+        return Err(Error::UnprocessableEntity(format!(
+            "since must be within the last {} days",
+            MAX_RECOVERY_AGE.num_days()
+        )));
+    }
+
+    db.with_tenant(tenant_id, |tx| Box::pin(claim_since(tx, tenant_id, since))).await
+}
+
+async fn claim_since(tx: &mut Transaction<'_, Postgres>, tenant_id: Uuid, since: DateTime<Utc>) -> Result<Vec<ClaimedFailure>> {
+    let rows = sqlx::query!(
+        r#"
+        UPDATE webhook_delivery_failures
+        SET recovered_at = NOW()
+        WHERE tenant_id = $1 AND produced_at >= $2 AND recovered_at IS NULL
+        RETURNING webhook_id, produced_at, alert_payload
+        "#,
+        tenant_id,
+        since,
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ClaimedFailure {
+            webhook_id: row.webhook_id,
+            produced_at: row.produced_at,
+            alert_payload: row.alert_payload,
+        })
+        .collect())
+}
@@ -0,0 +1,229 @@
+//! Type-state reporter for one delivery's lifecycle --
+//! `Queued -> Dispatched -> AttemptStarted(n) -> AttemptFailed(n, reason) | Delivered`,
+//! with [`AttemptFailed::retry`] looping back to the next `AttemptStarted`
+//! for [`super::delivery::deliver_with_retry`]'s backoff loop.
+//!
+//! Each transition consumes the previous stage, so the type system rules
+//! out things like reporting a second attempt before the first one
+//! started, or delivering twice. Every transition also persists a
+//! timestamped event (`GET /api/v1/webhooks/deliveries/:delivery_id/events`
+//! lists them for one delivery) -- that's best-effort logging, not part
+//! of the state machine, so a write failure is logged and swallowed
+//! rather than aborting the delivery it's only describing.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::db::TenantScopedPool;
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Stage {
+    Queued,
+    Dispatched,
+    AttemptStarted,
+    AttemptFailed,
+    Delivered,
+}
+
+impl Stage {
+    fn as_str(self) -> &'static str {
+        match self {
+            Stage::Queued => "queued",
+            Stage::Dispatched => "dispatched",
+            Stage::AttemptStarted => "attempt_started",
+            Stage::AttemptFailed => "attempt_failed",
+            Stage::Delivered => "delivered",
+        }
+    }
+}
+
+struct Ctx {
+    db: TenantScopedPool,
+    tenant_id: Uuid,
+    webhook_id: Uuid,
+    delivery_id: Uuid,
+}
+
+async fn record(ctx: &Ctx, stage: Stage, attempt: Option<u32>, detail: Option<String>) {
+    let result = ctx
+        .db
+        .with_tenant(ctx.tenant_id, |tx| {
+            Box::pin(insert(
+                tx,
+                ctx.tenant_id,
+                ctx.webhook_id,
+                ctx.delivery_id,
+                stage,
+                attempt.map(|n| n as i32),
+                detail,
+            ))
+        })
+        .await;
+
+    if let Err(error) = result {
+        tracing::warn!(delivery_id = %ctx.delivery_id, %error, "failed to record delivery lifecycle event");
+    }
+}
+
+async fn insert(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    webhook_id: Uuid,
+    delivery_id: Uuid,
+    stage: Stage,
+    attempt: Option<i32>,
+    detail: Option<String>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO webhook_delivery_events (id, tenant_id, webhook_id, delivery_id, stage, attempt, detail)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        Uuid::new_v4(),
+        tenant_id,
+        webhook_id,
+        delivery_id,
+        stage.as_str(),
+        attempt,
+        detail,
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// A delivery that's been decided on but hasn't started yet.
+pub struct Queued(Ctx);
+
+impl Queued {
+    pub fn new(db: TenantScopedPool, tenant_id: Uuid, webhook_id: Uuid) -> Self {
+        Self(Ctx {
+            db,
+            tenant_id,
+            webhook_id,
+            delivery_id: Uuid::new_v4(),
+        })
+    }
+
+    pub fn delivery_id(&self) -> Uuid {
+        self.0.delivery_id
+    }
+
+    /// Records that the queued delivery has started -- one HTTP attempt
+    /// loop is about to begin.
+    pub async fn dispatch(self) -> Dispatched {
+        record(&self.0, Stage::Queued, None, None).await;
+        record(&self.0, Stage::Dispatched, None, None).await;
+        Dispatched(self.0)
+    }
+}
+
+/// Dispatched, no attempt has gone out over the wire yet.
+pub struct Dispatched(Ctx);
+
+impl Dispatched {
+    /// Starts attempt number `attempt` (1-indexed).
+    pub async fn attempt(self, attempt: u32) -> AttemptStarted {
+        record(&self.0, Stage::AttemptStarted, Some(attempt), None).await;
+        AttemptStarted { ctx: self.0, attempt }
+    }
+}
+
+/// One attempt is in flight.
+pub struct AttemptStarted {
+    ctx: Ctx,
+    attempt: u32,
+}
+
+impl AttemptStarted {
+    pub fn attempt_number(&self) -> u32 {
+        self.attempt
+    }
+
+    /// The attempt succeeded -- terminal stage, nothing more to report.
+    pub async fn delivered(self) -> Delivered {
+        record(&self.ctx, Stage::Delivered, Some(self.attempt), None).await;
+        Delivered(self.ctx)
+    }
+
+    /// The attempt failed with `reason` (a status code, timeout, or
+    /// connection error -- whatever the caller observed).
+    pub async fn failed(self, reason: String) -> AttemptFailed {
+        record(&self.ctx, Stage::AttemptFailed, Some(self.attempt), Some(reason)).await;
+        AttemptFailed {
+            ctx: self.ctx,
+            attempt: self.attempt,
+        }
+    }
+}
+
+/// An attempt failed; the caller's backoff sleep happens between this
+/// and the retry (or the caller gives up and drops this token).
+pub struct AttemptFailed {
+    ctx: Ctx,
+    attempt: u32,
+}
+
+impl AttemptFailed {
+    pub fn attempt_number(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Starts the next attempt in the same delivery's retry loop.
+    pub async fn retry(self) -> AttemptStarted {
+        let attempt = self.attempt + 1;
+        record(&self.ctx, Stage::AttemptStarted, Some(attempt), None).await;
+        AttemptStarted { ctx: self.ctx, attempt }
+    }
+}
+
+/// Terminal: the delivery succeeded.
+pub struct Delivered(#[allow(dead_code)] Ctx);
+
+/// One stage transition, as listed back out for an operator.
+#[derive(Debug, Serialize)]
+pub struct DeliveryEvent {
+    pub stage: String,
+    pub attempt: Option<i32>,
+    pub detail: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Every recorded transition for one delivery, oldest first.
+pub async fn events_for(db: &TenantScopedPool, tenant_id: Uuid, delivery_id: Uuid) -> Result<Vec<DeliveryEvent>> {
+    db.with_tenant(tenant_id, |tx| Box::pin(fetch_events(tx, tenant_id, delivery_id)))
+        .await
+}
+
+async fn fetch_events(
+    tx: &mut Transaction<'_, Postgres>,
+    tenant_id: Uuid,
+    delivery_id: Uuid,
+) -> Result<Vec<DeliveryEvent>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT stage, attempt, detail, occurred_at
+        FROM webhook_delivery_events
+        WHERE tenant_id = $1 AND delivery_id = $2
+        ORDER BY occurred_at ASC
+        "#,
+        tenant_id,
+        delivery_id,
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| DeliveryEvent {
+            stage: row.stage,
+            attempt: row.attempt,
+            detail: row.detail,
+            occurred_at: row.occurred_at,
+        })
+        .collect())
+}
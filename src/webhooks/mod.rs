@@ -0,0 +1,31 @@
+//! Tenant-configurable webhook delivery for pushing alerts out to
+//! external SOAR/SIEM systems, alongside the live-client-only transports
+//! in [`crate::verdicts`].
+//!
+//! [`AlertPayload`] is the shape [`crate::correlation::CorrelationEngine`]
+//! hands to [`delivery::WebhookDispatcher::dispatch`] once a telemetry
+//! batch completes an attack-chain rule (see [`crate::telemetry`]'s
+//! ingest handler). Every delivery is HMAC-SHA256 signed (see
+//! [`signing`]) so a receiver can verify it actually came from here. A
+//! delivery that exhausts its retries is dead-lettered into [`failures`]
+//! rather than dropped, so an operator can recover it later. Every target
+//! is run past a [`crate::policy::PolicyEngine`] before delivery to keep a
+//! noisy or duplicate verdict from ever being sent, and past [`breaker`]
+//! so a persistently broken receiver stops burning a full retry budget on
+//! every new verdict once it trips.
+
+mod breaker;
+mod config;
+mod delivery;
+mod failures;
+mod lifecycle;
+mod routes;
+mod signing;
+
+pub use breaker::BreakerStatus;
+pub use config::WebhookRegistration;
+pub use delivery::{AlertPayload, WebhookDispatcher};
+pub use failures::FailedDelivery;
+pub use lifecycle::DeliveryEvent;
+pub use routes::router;
+pub use signing::{verify_signature, DEFAULT_TOLERANCE_SECS};
@@ -0,0 +1,175 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::breaker::{self, BreakerStatus};
+use super::config::{self, WebhookRegistration};
+use super::failures::{self, FailedDelivery};
+use super::lifecycle::{self, DeliveryEvent};
+use crate::auth::AuthenticatedUser;
+use crate::error::Result;
+use crate::policy::{self, Integration, PolicyDecisionRecord};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterWebhookRequest {
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecoverWebhooksRequest {
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecoverWebhooksResponse {
+    pub requeued: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListFailuresResponse {
+    pub failures: Vec<FailedDelivery>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListDeliveryEventsResponse {
+    pub events: Vec<DeliveryEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPolicyDecisionsResponse {
+    pub decisions: Vec<PolicyDecisionRecord>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/webhooks",
+            get(list_webhooks_handler).post(register_webhook_handler),
+        )
+        .route("/api/v1/webhooks/:webhook_id", delete(delete_webhook_handler))
+        .route("/api/v1/webhooks/:webhook_id/rotate", post(rotate_webhook_handler))
+        .route("/api/v1/webhooks/failures", get(list_failures_handler))
+        .route("/api/v1/webhooks/recover", post(recover_webhooks_handler))
+        .route(
+            "/api/v1/webhooks/deliveries/:delivery_id/events",
+            get(list_delivery_events_handler),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/policy-decisions",
+            get(list_policy_decisions_handler),
+        )
+        .route("/api/v1/webhooks/:webhook_id/breaker", get(breaker_status_handler))
+}
+
+async fn register_webhook_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Json(body): Json<RegisterWebhookRequest>,
+) -> Result<Json<WebhookRegistration>> {
+    user.require_scope("webhook:write")?;
+    let registration = config::register(&state.db, user.tenant_id, body.url).await?;
+    Ok(Json(registration))
+}
+
+async fn list_webhooks_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<WebhookRegistration>>> {
+    user.require_scope("webhook:read")?;
+    let webhooks = config::list(&state.db, user.tenant_id).await?;
+    Ok(Json(webhooks))
+}
+
+async fn delete_webhook_handler(
+    user: AuthenticatedUser,
+    Path(webhook_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<()> {
+    user.require_scope("webhook:write")?;
+    config::delete(&state.db, user.tenant_id, webhook_id).await
+}
+
+/// Rotates a webhook's signing secret and returns the new one -- shown
+/// once, like [`register_webhook_handler`]'s. The retiring secret keeps
+/// signing deliveries alongside the new one for [`config::rotate`]'s
+/// grace window, so there's no gap where a receiver mid-rollout can't
+/// verify anything.
+async fn rotate_webhook_handler(
+    user: AuthenticatedUser,
+    Path(webhook_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<WebhookRegistration>)> {
+    user.require_scope("webhook:write")?;
+    let registration = config::rotate(&state.db, user.tenant_id, webhook_id).await?;
+    Ok((StatusCode::OK, Json(registration)))
+}
+
+/// Lists deliveries still sitting in the dead-letter log, most recent
+/// failure first -- what [`recover_webhooks_handler`] re-drives.
+async fn list_failures_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+) -> Result<Json<ListFailuresResponse>> {
+    user.require_scope("webhook:read")?;
+    let failures = failures::list(&state.db, user.tenant_id).await?;
+    Ok(Json(ListFailuresResponse { failures }))
+}
+
+/// Re-drives every dead-lettered delivery produced at or after `since`
+/// through the normal retry pipeline -- the manual recovery lever for
+/// after a receiver comes back from an outage. Rejects a `since` older
+/// than [`failures`]'s age limit with 422, since failures that old are
+/// assumed purged.
+async fn recover_webhooks_handler(
+    user: AuthenticatedUser,
+    State(state): State<AppState>,
+    Json(body): Json<RecoverWebhooksRequest>,
+) -> Result<Json<RecoverWebhooksResponse>> {
+    user.require_scope("webhook:write")?;
+    let requeued = state.webhooks.recover(user.tenant_id, body.since).await?;
+    Ok(Json(RecoverWebhooksResponse { requeued }))
+}
+
+/// Lists one delivery's staged lifecycle events (queued, dispatched, each
+/// attempt started/failed, delivered), oldest first -- see
+/// [`lifecycle`] for the type-state reporter that produces them.
+async fn list_delivery_events_handler(
+    user: AuthenticatedUser,
+    Path(delivery_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<ListDeliveryEventsResponse>> {
+    user.require_scope("webhook:read")?;
+    let events = lifecycle::events_for(&state.db, user.tenant_id, delivery_id).await?;
+    Ok(Json(ListDeliveryEventsResponse { events }))
+}
+
+/// Lists the most recent Send/Suppress/Defer decisions the policy engine
+/// made for this webhook, most recent first -- see [`crate::policy`] for
+/// what drives them.
+async fn list_policy_decisions_handler(
+    user: AuthenticatedUser,
+    Path(webhook_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<ListPolicyDecisionsResponse>> {
+    user.require_scope("webhook:read")?;
+    let decisions = policy::list_decisions(&state.db, user.tenant_id, Integration::Webhook, webhook_id).await?;
+    Ok(Json(ListPolicyDecisionsResponse { decisions }))
+}
+
+/// Reports a webhook's circuit breaker state, consecutive failure count,
+/// and next probe time -- so an operator can tell an integration is
+/// quarantined instead of just seeing its verdicts stop arriving.
+async fn breaker_status_handler(
+    user: AuthenticatedUser,
+    Path(webhook_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<BreakerStatus>> {
+    user.require_scope("webhook:read")?;
+    let status = breaker::status(&state.db, user.tenant_id, webhook_id).await?;
+    Ok(Json(status))
+}
@@ -0,0 +1,107 @@
+//! HMAC-SHA256 request signing for webhook deliveries, so a receiver can
+//! verify a payload actually came from Sentinel rather than trusting the
+//! transport alone.
+//!
+//! The signed message is `"<unix_ts>.<raw_body>"` rather than the body
+//! by itself, so a captured delivery can't be replayed verbatim once its
+//! timestamp falls outside [`verify_signature`]'s tolerance; the
+//! timestamp travels in its own `X-Sentinel-Timestamp` header rather
+//! than being parsed back out of the signature. `X-Sentinel-Signature`
+//! can carry more than one `v1=` value, one per currently-valid secret,
+//! so a secret rotation ([`super::config::rotate`]) has a window where
+//! deliveries are signed with both the retiring and the new secret -- a
+//! receiver that's only updated its stored secret to one of the two
+//! still verifies.
+//!
+//! `hmac`'s pinned version expects a major-behind `digest` than the
+//! `sha2` this tree otherwise hashes with (see `Cargo.toml`), so this
+//! pulls in that older `sha2` under its own name rather than downgrading
+//! the crate's primary hashing dependency.
+
+use hmac::{Hmac, Mac};
+use hmac_sha256::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How far a delivery's timestamp may drift from "now" (either
+/// direction) before [`verify_signature`] rejects it as a possible
+/// replay.
+pub const DEFAULT_TOLERANCE_SECS: i64 = 300;
+
+fn keyed_mac(secret: &str, ts: i64, body: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(ts.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+/// The value of `X-Sentinel-Signature` for one delivery: one `v1=`
+/// entry per secret in `secrets`.
+pub fn signature_header(secrets: &[&str], ts: i64, body: &[u8]) -> String {
+    secrets
+        .iter()
+        .map(|secret| format!("v1={}", hex::encode(keyed_mac(secret, ts, body).finalize().into_bytes())))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Recomputes the MAC for `secret` in constant time and checks it
+/// against every `v1=` value in `header`, then rejects if `ts` is
+/// further than `tolerance_secs` from the current time in either
+/// direction.
+pub fn verify_signature(secret: &str, ts: i64, body: &[u8], header: &str, tolerance_secs: i64) -> bool {
+    let now = chrono::Utc::now().timestamp();
+    if (now - ts).abs() > tolerance_secs {
+        return false;
+    }
+
+    header
+        .split(',')
+        .filter_map(|part| part.trim().strip_prefix("v1="))
+        .any(|candidate| match hex::decode(candidate) {
+            Ok(bytes) => keyed_mac(secret, ts, body).verify_slice(&bytes).is_ok(),
+            Err(_) => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_single_secret() {
+        let ts = chrono::Utc::now().timestamp();
+        let header = signature_header(&["s3cret"], ts, b"payload");
+        assert!(verify_signature("s3cret", ts, b"payload", &header, DEFAULT_TOLERANCE_SECS));
+    }
+
+    #[test]
+    fn accepts_either_secret_during_rotation() {
+        let ts = chrono::Utc::now().timestamp();
+        let header = signature_header(&["old", "new"], ts, b"payload");
+        assert!(verify_signature("old", ts, b"payload", &header, DEFAULT_TOLERANCE_SECS));
+        assert!(verify_signature("new", ts, b"payload", &header, DEFAULT_TOLERANCE_SECS));
+    }
+
+    #[test]
+    fn rejects_wrong_secret() {
+        let ts = chrono::Utc::now().timestamp();
+        let header = signature_header(&["s3cret"], ts, b"payload");
+        assert!(!verify_signature("wrong", ts, b"payload", &header, DEFAULT_TOLERANCE_SECS));
+    }
+
+    #[test]
+    fn rejects_tampered_body() {
+        let ts = chrono::Utc::now().timestamp();
+        let header = signature_header(&["s3cret"], ts, b"payload");
+        assert!(!verify_signature("s3cret", ts, b"tampered", &header, DEFAULT_TOLERANCE_SECS));
+    }
+
+    #[test]
+    fn rejects_timestamp_outside_tolerance() {
+        let ts = chrono::Utc::now().timestamp() - DEFAULT_TOLERANCE_SECS - 1;
+        let header = signature_header(&["s3cret"], ts, b"payload");
+        assert!(!verify_signature("s3cret", ts, b"payload", &header, DEFAULT_TOLERANCE_SECS));
+    }
+}
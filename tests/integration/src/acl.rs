@@ -0,0 +1,176 @@
+//! Permission allow/deny-overwrite drift detector for resource ACLs.
+//!
+//! Modeled on the channel-style permission overwrite most RBAC-synced
+//! backends converge on: an `allow` bitset and a `deny` bitset scoped to
+//! either a role or a specific user, with a user-scoped overwrite taking
+//! precedence over a role-scoped one and, within a single overwrite, a
+//! deny bit winning over an allow bit for the same capability. Storing
+//! such an overwrite and then exercising the capability it targets (via
+//! caller-supplied `store`/`attempt` closures, since the endpoint shape
+//! varies by resource) catches servers whose effective-permission
+//! computation has drifted from what they just claimed to have stored --
+//! the ACL write succeeds but enforcement lags or inverts.
+
+use std::future::Future;
+
+use serde::Serialize;
+
+/// A capability gated by an overwrite, expressed as its bit position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Capability(pub u64);
+
+impl Capability {
+    pub const MANAGE_SETTINGS: Capability = Capability(1 << 0);
+    pub const DELETE_ARTIFACT: Capability = Capability(1 << 1);
+    pub const VIEW_ARTIFACT: Capability = Capability(1 << 2);
+}
+
+/// Whether an overwrite targets a role or a specific user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SubjectType {
+    Role,
+    User,
+}
+
+/// An allow/deny overwrite for one subject, the same shape channel
+/// permission overwrites use.
+#[derive(Debug, Clone, Serialize)]
+pub struct Overwrite {
+    pub subject_type: SubjectType,
+    pub subject_id: String,
+    pub allow: u64,
+    pub deny: u64,
+}
+
+impl Overwrite {
+    pub fn new(subject_type: SubjectType, subject_id: impl Into<String>) -> Self {
+        Self {
+            subject_type,
+            subject_id: subject_id.into(),
+            allow: 0,
+            deny: 0,
+        }
+    }
+
+    pub fn allowing(mut self, capability: Capability) -> Self {
+        self.allow |= capability.0;
+        self
+    }
+
+    pub fn denying(mut self, capability: Capability) -> Self {
+        self.deny |= capability.0;
+        self
+    }
+
+    /// `None` if this overwrite doesn't mention `capability` at all;
+    /// otherwise the deny bit wins over the allow bit, matching how a
+    /// single overwrite resolves a capability it sets both bits for.
+    fn decision(&self, capability: Capability) -> Option<bool> {
+        if self.deny & capability.0 != 0 {
+            Some(false)
+        } else if self.allow & capability.0 != 0 {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+/// The role- and/or user-scoped overwrites in effect for one probe.
+#[derive(Debug, Clone, Default)]
+pub struct OverwriteSpec {
+    pub role: Option<Overwrite>,
+    pub user: Option<Overwrite>,
+}
+
+impl OverwriteSpec {
+    /// The permission a correctly-implemented server should land on: the
+    /// user overwrite decides outright if it mentions the capability at
+    /// all, otherwise the role overwrite does.
+    pub fn effective_allow(&self, capability: Capability) -> bool {
+        self.user
+            .as_ref()
+            .and_then(|o| o.decision(capability))
+            .or_else(|| self.role.as_ref().and_then(|o| o.decision(capability)))
+            .unwrap_or(false)
+    }
+}
+
+/// A handful of named allow/deny combinations worth checking beyond the
+/// simple "deny refuses, allow grants" cases: same-overwrite conflicts
+/// and the two orderings of role-vs-user precedence.
+pub fn standard_conflict_cases(
+    role_id: &str,
+    user_id: &str,
+    capability: Capability,
+) -> Vec<(&'static str, OverwriteSpec)> {
+    vec![
+        (
+            "role denies and allows the same capability (deny should win)",
+            OverwriteSpec {
+                role: Some(
+                    Overwrite::new(SubjectType::Role, role_id)
+                        .allowing(capability)
+                        .denying(capability),
+                ),
+                user: None,
+            },
+        ),
+        (
+            "role allows, user-specific overwrite denies (user should win)",
+            OverwriteSpec {
+                role: Some(Overwrite::new(SubjectType::Role, role_id).allowing(capability)),
+                user: Some(Overwrite::new(SubjectType::User, user_id).denying(capability)),
+            },
+        ),
+        (
+            "role denies, user-specific overwrite allows (user should win)",
+            OverwriteSpec {
+                role: Some(Overwrite::new(SubjectType::Role, role_id).denying(capability)),
+                user: Some(Overwrite::new(SubjectType::User, user_id).allowing(capability)),
+            },
+        ),
+    ]
+}
+
+/// Drift between what an overwrite says should happen and what the
+/// server actually enforced.
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftFinding {
+    pub case: &'static str,
+    pub capability: Capability,
+    pub expected_allowed: bool,
+    pub observed_allowed: bool,
+}
+
+/// Stores `spec` via `store`, then exercises `capability` via `attempt`
+/// and reports a [`DriftFinding`] if the server's enforcement doesn't
+/// match `spec`'s [`OverwriteSpec::effective_allow`].
+pub async fn assert_enforced<Store, StoreFut, Attempt, AttemptFut>(
+    case: &'static str,
+    capability: Capability,
+    spec: &OverwriteSpec,
+    store: Store,
+    attempt: Attempt,
+) -> anyhow::Result<Option<DriftFinding>>
+where
+    Store: FnOnce() -> StoreFut,
+    StoreFut: Future<Output = anyhow::Result<()>>,
+    Attempt: FnOnce() -> AttemptFut,
+    AttemptFut: Future<Output = anyhow::Result<bool>>,
+{
+    store().await?;
+    let observed_allowed = attempt().await?;
+    let expected_allowed = spec.effective_allow(capability);
+
+    if observed_allowed == expected_allowed {
+        Ok(None)
+    } else {
+        Ok(Some(DriftFinding {
+            case,
+            capability,
+            expected_allowed,
+            observed_allowed,
+        }))
+    }
+}
@@ -0,0 +1,109 @@
+//! Mints real signed JWTs for the test suite.
+//!
+//! The suite drives Sentinel purely over HTTP, so it can't call the
+//! server's `sentinel::auth` module directly — it mirrors the same HS256
+//! claims shape and signing secret instead, so tokens minted here are
+//! accepted (or correctly rejected) by the server's auth middleware rather
+//! than by a handler trusting a fabricated string.
+
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+struct Claims {
+    sub: Uuid,
+    tenant_id: Uuid,
+    roles: Vec<String>,
+    scopes: Vec<String>,
+    jti: Uuid,
+    mfa_complete: bool,
+    exp: i64,
+}
+
+fn signing_key() -> String {
+    std::env::var("JWT_SECRET").unwrap_or_else(|_| "sentinel-dev-secret".to_string())
+}
+
+/// Issues a signed access token for `user_id` in `tenant_id`, carrying
+/// `scopes` such as `artifact:read` or `case:write`.
+pub fn issue_test_token(
+    user_id: Uuid,
+    tenant_id: Uuid,
+    roles: Vec<String>,
+    scopes: Vec<String>,
+) -> String {
+    issue_test_token_expiring_in(user_id, tenant_id, roles, scopes, Duration::hours(1))
+}
+
+/// Issues a signed access token identical to [`issue_test_token`] but
+/// already past its `exp`, for tests that need the server to reject a
+/// token on expiry rather than on signature or claim shape.
+pub fn issue_expired_test_token(
+    user_id: Uuid,
+    tenant_id: Uuid,
+    roles: Vec<String>,
+    scopes: Vec<String>,
+) -> String {
+    issue_test_token_expiring_in(user_id, tenant_id, roles, scopes, Duration::hours(-1))
+}
+
+fn issue_test_token_expiring_in(
+    user_id: Uuid,
+    tenant_id: Uuid,
+    roles: Vec<String>,
+    scopes: Vec<String>,
+    ttl: Duration,
+) -> String {
+    let claims = Claims {
+        sub: user_id,
+        tenant_id,
+        roles,
+        scopes,
+        jti: Uuid::new_v4(),
+        mfa_complete: true,
+        exp: (Utc::now() + ttl).timestamp(),
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(signing_key().as_bytes()),
+    )
+    .expect("signing a test token should never fail")
+}
+
+/// Issues a token exactly like a login that still needs a second factor
+/// would receive: no scopes, `mfa_complete: false`. Every scope-checked
+/// route rejects it regardless of what `roles` claims -- this is what
+/// tests use to confirm a partial token can't reach a privileged action.
+pub fn issue_partial_test_token(user_id: Uuid, tenant_id: Uuid, roles: Vec<String>) -> String {
+    let claims = Claims {
+        sub: user_id,
+        tenant_id,
+        roles,
+        scopes: Vec::new(),
+        jti: Uuid::new_v4(),
+        mfa_complete: false,
+        exp: (Utc::now() + Duration::minutes(5)).timestamp(),
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(signing_key().as_bytes()),
+    )
+    .expect("signing a test token should never fail")
+}
+
+/// The full scope set granted to an `analyst` role, for tests that don't
+/// care about per-scope rejection behavior.
+pub fn analyst_scopes() -> Vec<String> {
+    vec![
+        "artifact:read".to_string(),
+        "artifact:write".to_string(),
+        "verdict:read".to_string(),
+        "case:write".to_string(),
+    ]
+}
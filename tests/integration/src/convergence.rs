@@ -0,0 +1,72 @@
+//! Distributed role-propagation consistency check across replicas.
+//!
+//! Some backends fan role/permission updates out to multiple nodes
+//! (queriers/ingestors) and are only eventually consistent. After a
+//! privilege change lands on one node, [`check_convergence`] polls a
+//! caller-supplied list of peer node base URLs for the same resource and
+//! measures how long each one takes to reflect it -- a node that still
+//! honors a revoked permission past a configurable window is exactly the
+//! window an attacker could exploit after a role downgrade, not just a
+//! liveness blip.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use tokio::time::sleep;
+
+/// One peer node's convergence result.
+#[derive(Debug, Clone)]
+pub struct ConvergenceResult {
+    pub node_base_url: String,
+    pub converged: bool,
+    pub converged_after: Option<Duration>,
+}
+
+/// Polls every node in `nodes` with `check` -- which should return
+/// `Ok(true)` once that node reflects the change -- every `poll_interval`
+/// until it converges or `window` elapses. A node that never converges
+/// within `window` is reported with `converged: false`.
+pub async fn check_convergence<F, Fut>(
+    nodes: &[String],
+    window: Duration,
+    poll_interval: Duration,
+    check: F,
+) -> anyhow::Result<Vec<ConvergenceResult>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = anyhow::Result<bool>>,
+{
+    let mut results = Vec::with_capacity(nodes.len());
+
+    for node in nodes {
+        let started = Instant::now();
+        let mut converged = false;
+
+        loop {
+            if check(node.clone()).await? {
+                converged = true;
+                break;
+            }
+
+            let elapsed = started.elapsed();
+            if elapsed >= window {
+                break;
+            }
+            sleep(poll_interval.min(window - elapsed)).await;
+        }
+
+        results.push(ConvergenceResult {
+            node_base_url: node.clone(),
+            converged,
+            converged_after: converged.then(|| started.elapsed()),
+        });
+    }
+
+    Ok(results)
+}
+
+/// The findings worth failing a test over: every node that never
+/// converged within the configured window.
+pub fn lagging_nodes(results: &[ConvergenceResult]) -> Vec<&ConvergenceResult> {
+    results.iter().filter(|r| !r.converged).collect()
+}
@@ -0,0 +1,70 @@
+//! Mirrors the server's AES-256-GCM artifact sealing so the suite can prove
+//! cross-tenant decryption fails authentication without linking against
+//! `sentinel::crypto` directly (a tenant's real, KEK-wrapped DEK is never
+//! exposed to a client, so a black-box test can't retrieve it anyway — this
+//! exercises the same cryptographic property with locally generated keys).
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use rand::RngCore;
+use uuid::Uuid;
+
+pub struct SealedBlob {
+    pub nonce: [u8; 12],
+    pub ciphertext: Vec<u8>,
+}
+
+pub fn generate_dek() -> [u8; 32] {
+    let mut dek = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut dek);
+    dek
+}
+
+pub fn seal(dek: &[u8; 32], tenant_id: Uuid, artifact_id: Uuid, plaintext: &[u8]) -> SealedBlob {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(dek));
+
+    let mut nonce_bytes = [0u8; 12];
+    rand::rngs::OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let aad = associated_data(tenant_id, artifact_id);
+    let ciphertext = cipher
+        .encrypt(nonce, Payload { msg: plaintext, aad: &aad })
+        .expect("sealing test plaintext should never fail");
+
+    SealedBlob {
+        nonce: nonce_bytes,
+        ciphertext,
+    }
+}
+
+/// Returns `None` if `sealed` fails GCM authentication under `dek` —
+/// which is what should happen when it was sealed with a different
+/// tenant's key.
+pub fn open(
+    dek: &[u8; 32],
+    tenant_id: Uuid,
+    artifact_id: Uuid,
+    sealed: &SealedBlob,
+) -> Option<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(dek));
+    let nonce = Nonce::from_slice(&sealed.nonce);
+    let aad = associated_data(tenant_id, artifact_id);
+
+    cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: &sealed.ciphertext,
+                aad: &aad,
+            },
+        )
+        .ok()
+}
+
+fn associated_data(tenant_id: Uuid, artifact_id: Uuid) -> [u8; 32] {
+    let mut aad = [0u8; 32];
+    aad[..16].copy_from_slice(tenant_id.as_bytes());
+    aad[16..].copy_from_slice(artifact_id.as_bytes());
+    aad
+}
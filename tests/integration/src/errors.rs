@@ -0,0 +1,145 @@
+//! Error classification and budgeted failure handling for the
+//! load-testing suite.
+//!
+//! A single `failure_count` can't tell a client-side timeout from a
+//! connection refusal from an HTTP 500, and a run that quietly tolerates
+//! thousands of timeouts but still clears the final success-rate bar
+//! reports a false green. [`ErrorClass`] buckets a failure by kind,
+//! [`ErrorCounters`] tallies them per-class with atomics so concurrent
+//! tasks can record without contention, and [`ErrorBudget`] lets a
+//! caller treat timeouts as fatal (abort the run the moment one occurs)
+//! or budgeted (abort once a configured fraction of attempted requests
+//! have timed out).
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    Timeout,
+    Connect,
+    HttpStatus(u16),
+    Decode,
+}
+
+impl ErrorClass {
+    /// Classifies a `reqwest::Error` using its own `is_timeout`/`is_connect`/
+    /// `is_decode` probes, falling back to `Connect` for anything reqwest
+    /// doesn't categorize (e.g. a request-builder error).
+    pub fn from_reqwest_error(err: &reqwest::Error) -> Self {
+        if err.is_timeout() {
+            ErrorClass::Timeout
+        } else if err.is_connect() {
+            ErrorClass::Connect
+        } else if err.is_decode() {
+            ErrorClass::Decode
+        } else if let Some(status) = err.status() {
+            ErrorClass::HttpStatus(status.as_u16())
+        } else {
+            ErrorClass::Connect
+        }
+    }
+}
+
+/// Per-class failure counters. Each concurrent task records directly
+/// into the same `ErrorCounters` (shared behind an `Arc`) since the
+/// atomics make that contention-free, unlike the latency histograms in
+/// [`crate::latency`] which are merged per-task instead.
+#[derive(Default)]
+pub struct ErrorCounters {
+    timeout: AtomicU64,
+    connect: AtomicU64,
+    http_status: AtomicU64,
+    decode: AtomicU64,
+}
+
+impl ErrorCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, class: ErrorClass) {
+        let counter = match class {
+            ErrorClass::Timeout => &self.timeout,
+            ErrorClass::Connect => &self.connect,
+            ErrorClass::HttpStatus(_) => &self.http_status,
+            ErrorClass::Decode => &self.decode,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn timeout_count(&self) -> u64 {
+        self.timeout.load(Ordering::Relaxed)
+    }
+
+    pub fn connect_count(&self) -> u64 {
+        self.connect.load(Ordering::Relaxed)
+    }
+
+    pub fn http_status_count(&self) -> u64 {
+        self.http_status.load(Ordering::Relaxed)
+    }
+
+    pub fn decode_count(&self) -> u64 {
+        self.decode.load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.timeout_count() + self.connect_count() + self.http_status_count() + self.decode_count()
+    }
+
+    pub fn report(&self, label: &str) {
+        println!(
+            "  {label} errors: timeout={} connect={} http_status={} decode={} (total={})",
+            self.timeout_count(),
+            self.connect_count(),
+            self.http_status_count(),
+            self.decode_count(),
+            self.total(),
+        );
+    }
+}
+
+/// Configures how timeouts should be treated relative to the total
+/// number of requests attempted so far.
+pub struct ErrorBudget {
+    pub fatal_timeouts: bool,
+    pub max_timeout_fraction: f64,
+}
+
+impl ErrorBudget {
+    pub fn new(fatal_timeouts: bool, max_timeout_fraction: f64) -> Self {
+        Self {
+            fatal_timeouts,
+            max_timeout_fraction,
+        }
+    }
+
+    /// Returns `Err` describing why the run should abort: either
+    /// `fatal_timeouts` is set and a timeout has already occurred, or the
+    /// timeout fraction of `attempted` requests so far exceeds the
+    /// configured budget.
+    pub fn check(&self, counters: &ErrorCounters, attempted: u64) -> Result<(), String> {
+        let timeouts = counters.timeout_count();
+
+        if self.fatal_timeouts && timeouts > 0 {
+            return Err(format!(
+                "fatal_timeouts is set and {timeouts} timeout(s) occurred"
+            ));
+        }
+
+        if attempted == 0 {
+            return Ok(());
+        }
+
+        let timeout_fraction = timeouts as f64 / attempted as f64;
+        if timeout_fraction > self.max_timeout_fraction {
+            return Err(format!(
+                "{:.2}% of {attempted} requests timed out, exceeding the {:.2}% budget",
+                timeout_fraction * 100.0,
+                self.max_timeout_fraction * 100.0
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! Header-based privilege-escalation fuzzer generalizing the old
+//! single-header `make_request_with_role_override` probe.
+//!
+//! Drives a matrix of (endpoint x header x role) combinations under a
+//! single low-privilege token, comparing each injected-header response
+//! against a header-free baseline taken from the same endpoint. Any case
+//! where the header changed the status code is reported as a
+//! header-based authorization bypass; findings that differ only by which
+//! role value triggered the identical status change are deduplicated,
+//! and the survivors are ranked write-before-read.
+
+use std::collections::HashSet;
+
+use reqwest::{Client, Method, StatusCode};
+use serde::Serialize;
+
+/// Severity ranking for a confirmed bypass -- a write endpoint that
+/// honors a spoofed role header is worse than a read endpoint doing the
+/// same. Derives `Ord` so findings can be sorted write-first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Severity {
+    Read,
+    Write,
+}
+
+/// One endpoint under test.
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub method: Method,
+    pub path: String,
+    pub severity: Severity,
+}
+
+/// Candidate headers RBAC-synced backends have been seen trusting in
+/// place of the token's own bound role.
+pub const CANDIDATE_HEADERS: &[&str] = &["X-Role-Override", "X-Forwarded-Role", "X-User-Role"];
+
+/// Tiered role names to try in each candidate header.
+pub const CANDIDATE_ROLES: &[&str] = &["reader", "writer", "editor", "admin"];
+
+/// One confirmed header-based authorization bypass.
+#[derive(Debug, Clone, Serialize)]
+pub struct EscalationFinding {
+    pub method: String,
+    pub path: String,
+    pub header: String,
+    pub role: String,
+    pub severity: Severity,
+    pub baseline_status: u16,
+    pub escalated_status: u16,
+}
+
+/// Runs the full (target x header x role) matrix under `token`, returning
+/// one deduplicated, severity-ranked [`EscalationFinding`] per header
+/// that changed the response relative to the header-free baseline for
+/// that endpoint.
+pub async fn scan(
+    client: &Client,
+    base_url: &str,
+    token: &str,
+    targets: &[Target],
+) -> anyhow::Result<Vec<EscalationFinding>> {
+    let mut findings = Vec::new();
+    let mut seen = HashSet::new();
+
+    for target in targets {
+        let baseline_status = send(client, base_url, token, target, None).await?;
+
+        for &header in CANDIDATE_HEADERS {
+            for &role in CANDIDATE_ROLES {
+                let escalated_status = send(client, base_url, token, target, Some((header, role))).await?;
+
+                if escalated_status == baseline_status {
+                    continue;
+                }
+
+                // Roles that land on the exact same status change as a
+                // header already reported for this endpoint are the same
+                // underlying bypass -- only the first role to trigger it
+                // is kept.
+                let key = (
+                    target.method.as_str().to_string(),
+                    target.path.clone(),
+                    header.to_string(),
+                    escalated_status.as_u16(),
+                );
+                if !seen.insert(key) {
+                    continue;
+                }
+
+                findings.push(EscalationFinding {
+                    method: target.method.as_str().to_string(),
+                    path: target.path.clone(),
+                    header: header.to_string(),
+                    role: role.to_string(),
+                    severity: target.severity,
+                    baseline_status: baseline_status.as_u16(),
+                    escalated_status: escalated_status.as_u16(),
+                });
+            }
+        }
+    }
+
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    Ok(findings)
+}
+
+async fn send(
+    client: &Client,
+    base_url: &str,
+    token: &str,
+    target: &Target,
+    header_and_role: Option<(&str, &str)>,
+) -> anyhow::Result<StatusCode> {
+    let mut request = client
+        .request(target.method.clone(), format!("{base_url}{}", target.path))
+        .header("Authorization", format!("Bearer {token}"));
+
+    if let Some((header, role)) = header_and_role {
+        request = request.header(header, role);
+    }
+
+    Ok(request.send().await?.status())
+}
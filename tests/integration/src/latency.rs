@@ -0,0 +1,142 @@
+//! Lock-free-per-task latency recording for the load-testing suite.
+//!
+//! Each concurrent task records into its own [`LatencyRecorder`] (an HDR
+//! histogram) instead of contending on a shared one; [`LatencyRecorder::merge`]
+//! folds them back together once every task has returned. Buckets span 1
+//! microsecond to 60 seconds at 3 significant digits -- enough resolution
+//! to tell a 5ms p99 from a 6ms one without the memory cost of a linear
+//! histogram over the same range.
+//!
+//! [`OpenLoopSchedule`] is what makes the recorded latency mean anything
+//! under load: a closed-loop harness that fires the next request only
+//! once the last one returns can't see a stall, because every request
+//! that piled up behind it is simply never sent, so it's never measured
+//! as slow (coordinated omission). Deriving each request's intended send
+//! time from a fixed-rate schedule instead, and recording `now -
+//! intended_send_time` rather than the request's own wall-clock duration,
+//! means a stall inflates the latency of every request queued up behind
+//! it, not just the one unlucky enough to be in flight when it happened.
+
+use std::time::{Duration, Instant};
+
+use hdrhistogram::Histogram;
+
+const MIN_VALUE_US: u64 = 1;
+const MAX_VALUE_US: u64 = 60_000_000; // 60s
+const SIGNIFICANT_DIGITS: u8 = 3;
+
+pub struct LatencyRecorder {
+    histogram: Histogram<u64>,
+}
+
+impl LatencyRecorder {
+    pub fn new() -> Self {
+        Self {
+            histogram: Histogram::new_with_bounds(MIN_VALUE_US, MAX_VALUE_US, SIGNIFICANT_DIGITS)
+                .expect("1us-60s at 3 significant digits is always valid histogram bounds"),
+        }
+    }
+
+    /// Records one request's latency, clamped into the histogram's range
+    /// so a pathological stall can't abort the whole run.
+    pub fn record(&mut self, latency: Duration) {
+        let micros = latency
+            .as_micros()
+            .clamp(MIN_VALUE_US as u128, MAX_VALUE_US as u128) as u64;
+        self.histogram
+            .record(micros)
+            .expect("value was just clamped into range");
+    }
+
+    /// Folds `other`'s recordings into `self`. Each concurrent task
+    /// should own its own recorder while it's running; merge them here
+    /// afterward instead of sharing one histogram across tasks.
+    pub fn merge(&mut self, other: &LatencyRecorder) {
+        self.histogram
+            .add(&other.histogram)
+            .expect("recorders created with LatencyRecorder::new share bounds and sigfig");
+    }
+
+    pub fn count(&self) -> u64 {
+        self.histogram.len()
+    }
+
+    pub fn p50(&self) -> Duration {
+        self.quantile(0.50)
+    }
+
+    pub fn p90(&self) -> Duration {
+        self.quantile(0.90)
+    }
+
+    pub fn p99(&self) -> Duration {
+        self.quantile(0.99)
+    }
+
+    pub fn p999(&self) -> Duration {
+        self.quantile(0.999)
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.histogram.max())
+    }
+
+    fn quantile(&self, q: f64) -> Duration {
+        Duration::from_micros(self.histogram.value_at_quantile(q))
+    }
+
+    pub fn report(&self, label: &str) {
+        println!(
+            "  {label} latency: p50={:?} p90={:?} p99={:?} p99.9={:?} max={:?} (n={})",
+            self.p50(),
+            self.p90(),
+            self.p99(),
+            self.p999(),
+            self.max(),
+            self.count(),
+        );
+    }
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fixed-rate open-loop schedule: the `n`th request's *intended* send
+/// time is `start + n / rate`, derived purely from the schedule and
+/// completely independent of when request `n - 1` actually finished.
+pub struct OpenLoopSchedule {
+    start: Instant,
+    interval: Duration,
+}
+
+impl OpenLoopSchedule {
+    pub fn new(rate_per_sec: u64) -> Self {
+        assert!(rate_per_sec > 0, "rate_per_sec must be positive");
+        Self {
+            start: Instant::now(),
+            interval: Duration::from_secs_f64(1.0 / rate_per_sec as f64),
+        }
+    }
+
+    /// The intended send time for the `n`th request (0-indexed) under
+    /// this schedule, regardless of when it actually gets sent.
+    pub fn intended_send_time(&self, n: u64) -> Instant {
+        self.start + self.interval * n as u32
+    }
+
+    /// Sleeps until it's `n`'s turn, then returns its intended send time
+    /// for the caller to measure latency against. If the schedule is
+    /// already behind (the generator itself is stalling), this returns
+    /// immediately without sleeping -- that lateness is exactly what the
+    /// latency recorded against the returned instant will capture.
+    pub async fn wait_for_turn(&self, n: u64) -> Instant {
+        let intended = self.intended_send_time(n);
+        if let Some(delay) = intended.checked_duration_since(Instant::now()) {
+            tokio::time::sleep(delay).await;
+        }
+        intended
+    }
+}
@@ -0,0 +1,19 @@
+//! Shared helpers for Sentinel's black-box integration test suite.
+//!
+//! The tests under `tests/` drive a running Sentinel deployment purely over
+//! HTTP/WebSocket and never link against the `sentinel` crate directly, so
+//! this library only holds test-harness infrastructure (latency recording,
+//! report formats, security probes) that multiple test binaries share.
+
+pub mod acl;
+pub mod auth;
+pub mod bola;
+pub mod convergence;
+pub mod crypto;
+pub mod errors;
+pub mod escalation;
+pub mod latency;
+pub mod mock_webhook;
+pub mod oauth;
+pub mod report;
+pub mod runtime_matrix;
@@ -0,0 +1,125 @@
+//! An in-process HTTP endpoint that stands in for a tenant's SOAR/SIEM
+//! webhook receiver, so tests can assert on what Sentinel actually
+//! delivered (body + `X-Sentinel-Timestamp`/`X-Sentinel-Signature`
+//! headers) instead of only polling an alerts endpoint.
+//!
+//! [`ReceivedWebhook::verify`] reimplements the HMAC-SHA256 check
+//! independently of `sentinel::webhooks::verify_signature` rather than
+//! calling it -- this crate never links against the `sentinel` crate
+//! (see the module doc on [`crate`]), the same as a real external SIEM
+//! wouldn't.
+
+use std::sync::{Arc, Mutex};
+
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::routing::post;
+use axum::Router;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// One delivery received by the mock receiver.
+#[derive(Debug, Clone)]
+pub struct ReceivedWebhook {
+    pub body: Vec<u8>,
+    pub timestamp: Option<i64>,
+    pub signature: Option<String>,
+}
+
+impl ReceivedWebhook {
+    /// Recomputes `HMAC-SHA256(secret, "<timestamp>.<body>")` and checks
+    /// it against every `v1=` value in `signature`, the same scheme
+    /// `sentinel::webhooks::verify_signature` implements.
+    pub fn verify(&self, secret: &str) -> bool {
+        let (Some(timestamp), Some(signature)) = (self.timestamp, &self.signature) else {
+            return false;
+        };
+
+        signature
+            .split(',')
+            .filter_map(|part| part.trim().strip_prefix("v1="))
+            .any(|candidate| {
+                let Ok(expected) = hex::decode(candidate) else {
+                    return false;
+                };
+                let mut mac =
+                    HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+                mac.update(timestamp.to_string().as_bytes());
+                mac.update(b".");
+                mac.update(&self.body);
+                mac.verify_slice(&expected).is_ok()
+            })
+    }
+}
+
+/// A running mock webhook receiver. Dropping this stops the server.
+pub struct MockWebhookReceiver {
+    pub addr: std::net::SocketAddr,
+    received: Arc<Mutex<Vec<ReceivedWebhook>>>,
+    server: JoinHandle<()>,
+}
+
+impl MockWebhookReceiver {
+    /// Binds to an ephemeral local port and starts accepting deliveries
+    /// immediately; register `self.url()` as a tenant's webhook.
+    pub async fn start() -> Self {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let app = Router::new()
+            .route("/webhook", post(receive_handler))
+            .with_state(received.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind ephemeral port");
+        let addr = listener.local_addr().expect("local addr");
+
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+
+        Self {
+            addr,
+            received,
+            server,
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}/webhook", self.addr)
+    }
+
+    pub fn received(&self) -> Vec<ReceivedWebhook> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockWebhookReceiver {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+async fn receive_handler(
+    State(received): State<Arc<Mutex<Vec<ReceivedWebhook>>>>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> axum::http::StatusCode {
+    let signature = headers
+        .get("X-Sentinel-Signature")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let timestamp = headers
+        .get("X-Sentinel-Timestamp")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+
+    received.lock().unwrap().push(ReceivedWebhook {
+        body: body.to_vec(),
+        timestamp,
+        signature,
+    });
+
+    axum::http::StatusCode::OK
+}
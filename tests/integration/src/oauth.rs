@@ -0,0 +1,227 @@
+//! OAuth2/OpenID Connect token acquisition for driving probes against a
+//! real IdP (e.g. Keycloak) instead of the locally-minted test JWTs in
+//! [`crate::auth`].
+//!
+//! [`CredentialProvider`] mints tokens via the `password`,
+//! `client_credentials`, or `authorization_code` grants, can locate its
+//! token endpoint from a realm base URL via OIDC discovery, and caches
+//! the result so probes don't re-authenticate on every request. A cached
+//! token is refreshed transparently once it expires, and
+//! [`CredentialProvider::send_with_refresh`] retries a single `401` mid-scan,
+//! so a long-running probe suite doesn't fail halfway through because a
+//! token aged out.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+/// Which OAuth2 grant to mint a fresh token with, once the cache is empty
+/// or a refresh token isn't honored by the IdP.
+#[derive(Debug, Clone)]
+pub enum Grant {
+    Password { username: String, password: String },
+    ClientCredentials,
+    AuthorizationCode { code: String, redirect_uri: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscoveryDocument {
+    token_endpoint: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+struct CachedToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Instant,
+}
+
+/// Mints and caches access tokens from an OAuth2/OIDC token endpoint.
+pub struct CredentialProvider {
+    http: Client,
+    token_endpoint: String,
+    client_id: String,
+    client_secret: Option<String>,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+impl CredentialProvider {
+    /// Builds a provider against an explicit token endpoint, skipping
+    /// discovery.
+    pub fn new(
+        token_endpoint: impl Into<String>,
+        client_id: impl Into<String>,
+        client_secret: Option<String>,
+    ) -> Self {
+        Self {
+            http: Client::new(),
+            token_endpoint: token_endpoint.into(),
+            client_id: client_id.into(),
+            client_secret,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Builds a provider by fetching
+    /// `{realm_base_url}/.well-known/openid-configuration` and reading its
+    /// `token_endpoint`, matching how Keycloak and other OIDC-compliant
+    /// IdPs publish their realm metadata.
+    pub async fn discover(
+        realm_base_url: &str,
+        client_id: impl Into<String>,
+        client_secret: Option<String>,
+    ) -> Result<Self> {
+        let http = Client::new();
+        let discovery_url = format!(
+            "{}/.well-known/openid-configuration",
+            realm_base_url.trim_end_matches('/')
+        );
+        let doc: DiscoveryDocument = http
+            .get(&discovery_url)
+            .send()
+            .await
+            .context("fetching OIDC discovery document")?
+            .error_for_status()
+            .context("OIDC discovery document request failed")?
+            .json()
+            .await
+            .context("parsing OIDC discovery document")?;
+
+        Ok(Self {
+            http,
+            token_endpoint: doc.token_endpoint,
+            client_id: client_id.into(),
+            client_secret,
+            cached: Mutex::new(None),
+        })
+    }
+
+    /// Returns a cached access token if it's still valid, otherwise mints
+    /// a fresh one -- via the cached refresh token if one is on hand,
+    /// falling back to `grant` otherwise.
+    pub async fn token(&self, grant: &Grant) -> Result<String> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref() {
+            if token.expires_at > Instant::now() {
+                return Ok(token.access_token.clone());
+            }
+        }
+
+        let refresh_token = cached.as_ref().and_then(|t| t.refresh_token.clone());
+        let response = match refresh_token {
+            Some(refresh_token) => {
+                self.request_token(&[
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", &refresh_token),
+                    ("client_id", &self.client_id),
+                ])
+                .await?
+            }
+            None => self.request_token_for_grant(grant).await?,
+        };
+
+        let access_token = response.access_token.clone();
+        *cached = Some(CachedToken {
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            expires_at: Instant::now() + Duration::from_secs(response.expires_in.unwrap_or(300)),
+        });
+        Ok(access_token)
+    }
+
+    /// Discards the cache and mints a fresh token via `grant`, bypassing
+    /// any refresh token -- used when a request comes back `401` mid-scan
+    /// and the cached (or refreshed) token turned out to be no good.
+    pub async fn reauthenticate(&self, grant: &Grant) -> Result<String> {
+        *self.cached.lock().await = None;
+        self.token(grant).await
+    }
+
+    /// Builds and sends a request via `build`, handing it the current
+    /// token. If the server answers `401`, mints a fresh token and
+    /// retries exactly once, so a token expiring mid-scan doesn't fail
+    /// the whole probe run.
+    pub async fn send_with_refresh<F>(&self, grant: &Grant, build: F) -> Result<Response>
+    where
+        F: Fn(&str) -> RequestBuilder,
+    {
+        let token = self.token(grant).await?;
+        let response = build(&token)
+            .send()
+            .await
+            .context("sending authenticated request")?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let token = self.reauthenticate(grant).await?;
+        build(&token)
+            .send()
+            .await
+            .context("retrying authenticated request after token refresh")
+    }
+
+    async fn request_token_for_grant(&self, grant: &Grant) -> Result<TokenResponse> {
+        match grant {
+            Grant::Password { username, password } => {
+                self.request_token(&[
+                    ("grant_type", "password"),
+                    ("username", username),
+                    ("password", password),
+                    ("client_id", &self.client_id),
+                ])
+                .await
+            }
+            Grant::ClientCredentials => {
+                self.request_token(&[
+                    ("grant_type", "client_credentials"),
+                    ("client_id", &self.client_id),
+                ])
+                .await
+            }
+            Grant::AuthorizationCode { code, redirect_uri } => {
+                self.request_token(&[
+                    ("grant_type", "authorization_code"),
+                    ("code", code),
+                    ("redirect_uri", redirect_uri),
+                    ("client_id", &self.client_id),
+                ])
+                .await
+            }
+        }
+    }
+
+    async fn request_token(&self, params: &[(&str, &str)]) -> Result<TokenResponse> {
+        let mut form = params.to_vec();
+        if let Some(secret) = &self.client_secret {
+            form.push(("client_secret", secret));
+        }
+
+        let response = self
+            .http
+            .post(&self.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .context("sending OAuth2 token request")?;
+
+        if !response.status().is_success() {
+            bail!("token endpoint returned {}", response.status());
+        }
+
+        response
+            .json()
+            .await
+            .context("parsing OAuth2 token response")
+    }
+}
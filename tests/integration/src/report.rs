@@ -0,0 +1,248 @@
+//! Machine-readable result export and baseline regression gating for the
+//! load-testing suite.
+//!
+//! Printing percentiles and error counts to stdout (as [`crate::latency`]
+//! and [`crate::errors`] do) is fine for a human watching `cargo test`
+//! run, but it gives CI nothing to diff against. [`LoadTestReport`]
+//! captures one scenario's result as JSON under a results directory;
+//! [`RegressionTolerance::check`] loads the previous report for that
+//! scenario and fails the comparison if p99 latency or throughput has
+//! regressed beyond a tolerance, so these `--ignored` load tests can run
+//! as CI regression guards instead of only ever being eyeballed by hand.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ErrorCounters;
+use crate::latency::LatencyRecorder;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyPercentilesMicros {
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub p999: u64,
+    pub max: u64,
+}
+
+impl LatencyPercentilesMicros {
+    fn from_recorder(recorder: &LatencyRecorder) -> Self {
+        Self {
+            p50: recorder.p50().as_micros() as u64,
+            p90: recorder.p90().as_micros() as u64,
+            p99: recorder.p99().as_micros() as u64,
+            p999: recorder.p999().as_micros() as u64,
+            max: recorder.max().as_micros() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorBreakdown {
+    pub timeout: u64,
+    pub connect: u64,
+    pub http_status: u64,
+    pub decode: u64,
+    pub total: u64,
+}
+
+impl ErrorBreakdown {
+    fn from_counters(counters: &ErrorCounters) -> Self {
+        Self {
+            timeout: counters.timeout_count(),
+            connect: counters.connect_count(),
+            http_status: counters.http_status_count(),
+            decode: counters.decode_count(),
+            total: counters.total(),
+        }
+    }
+}
+
+/// A single load-test scenario's result, serialized as JSON so it can be
+/// diffed against a prior run by CI rather than only read off stdout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadTestReport {
+    pub scenario: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub git_sha: String,
+    pub duration_secs: f64,
+    pub throughput_per_sec: f64,
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub latency: LatencyPercentilesMicros,
+    pub errors: ErrorBreakdown,
+}
+
+impl LoadTestReport {
+    pub fn new(
+        scenario: &str,
+        duration: Duration,
+        throughput_per_sec: f64,
+        success_count: u64,
+        failure_count: u64,
+        latency: &LatencyRecorder,
+        errors: &ErrorCounters,
+    ) -> Self {
+        Self {
+            scenario: scenario.to_string(),
+            timestamp: chrono::Utc::now(),
+            git_sha: current_git_sha(),
+            duration_secs: duration.as_secs_f64(),
+            throughput_per_sec,
+            success_count,
+            failure_count,
+            latency: LatencyPercentilesMicros::from_recorder(latency),
+            errors: ErrorBreakdown::from_counters(errors),
+        }
+    }
+
+    /// Writes this report as `{dir}/{scenario}.latest.json`, creating
+    /// `dir` if needed. Returns the path written to.
+    pub fn save(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.latest.json", self.scenario));
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// Copies this report to `{dir}/{scenario}.baseline.json`, the file
+    /// [`load_baseline`] reads back on subsequent runs. Call this to
+    /// establish or intentionally update a scenario's baseline.
+    pub fn save_as_baseline(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.baseline.json", self.scenario));
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// Renders this report in Prometheus text exposition format, labeled
+    /// by scenario and git SHA, for scraping or pushing to a pushgateway.
+    pub fn to_prometheus_text(&self) -> String {
+        let labels = format!(
+            "scenario=\"{}\",git_sha=\"{}\"",
+            self.scenario, self.git_sha
+        );
+        format!(
+            "# TYPE load_test_throughput_per_sec gauge\n\
+             load_test_throughput_per_sec{{{labels}}} {throughput}\n\
+             # TYPE load_test_duration_seconds gauge\n\
+             load_test_duration_seconds{{{labels}}} {duration}\n\
+             # TYPE load_test_success_count gauge\n\
+             load_test_success_count{{{labels}}} {success}\n\
+             # TYPE load_test_failure_count gauge\n\
+             load_test_failure_count{{{labels}}} {failure}\n\
+             # TYPE load_test_latency_microseconds gauge\n\
+             load_test_latency_microseconds{{{labels},quantile=\"0.5\"}} {p50}\n\
+             load_test_latency_microseconds{{{labels},quantile=\"0.9\"}} {p90}\n\
+             load_test_latency_microseconds{{{labels},quantile=\"0.99\"}} {p99}\n\
+             load_test_latency_microseconds{{{labels},quantile=\"0.999\"}} {p999}\n\
+             # TYPE load_test_errors_total gauge\n\
+             load_test_errors_total{{{labels},class=\"timeout\"}} {err_timeout}\n\
+             load_test_errors_total{{{labels},class=\"connect\"}} {err_connect}\n\
+             load_test_errors_total{{{labels},class=\"http_status\"}} {err_http_status}\n\
+             load_test_errors_total{{{labels},class=\"decode\"}} {err_decode}\n",
+            throughput = self.throughput_per_sec,
+            duration = self.duration_secs,
+            success = self.success_count,
+            failure = self.failure_count,
+            p50 = self.latency.p50,
+            p90 = self.latency.p90,
+            p99 = self.latency.p99,
+            p999 = self.latency.p999,
+            err_timeout = self.errors.timeout,
+            err_connect = self.errors.connect,
+            err_http_status = self.errors.http_status,
+            err_decode = self.errors.decode,
+        )
+    }
+
+    /// Pushes this report to a Prometheus pushgateway at `pushgateway_url`
+    /// (e.g. `http://localhost:9091`) under job `sentinel_load_test` and
+    /// instance `self.scenario`, so a live dashboard can scrape the run
+    /// the same way perf runs ship step metrics to Prometheus.
+    pub async fn push_to_gateway(&self, pushgateway_url: &str) -> Result<(), reqwest::Error> {
+        let url = format!(
+            "{}/metrics/job/sentinel_load_test/instance/{}",
+            pushgateway_url.trim_end_matches('/'),
+            self.scenario
+        );
+        reqwest::Client::new()
+            .put(url)
+            .body(self.to_prometheus_text())
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Loads `{dir}/{scenario}.baseline.json` if it exists. Returns `None`
+/// (rather than an error) when there's no baseline yet, since the first
+/// run of a new scenario has nothing to compare against.
+pub fn load_baseline(dir: &Path, scenario: &str) -> Option<LoadTestReport> {
+    let path = dir.join(format!("{}.baseline.json", scenario));
+    let bytes = std::fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Regression thresholds relative to a baseline report.
+pub struct RegressionTolerance {
+    pub max_p99_ratio: f64,
+    pub min_throughput_ratio: f64,
+}
+
+impl RegressionTolerance {
+    pub fn new(max_p99_ratio: f64, min_throughput_ratio: f64) -> Self {
+        Self {
+            max_p99_ratio,
+            min_throughput_ratio,
+        }
+    }
+
+    /// Returns `Err` describing the regression if `current`'s p99 exceeds
+    /// `max_p99_ratio` times the baseline's p99, or its throughput falls
+    /// below `min_throughput_ratio` times the baseline's throughput.
+    pub fn check(&self, current: &LoadTestReport, baseline: &LoadTestReport) -> Result<(), String> {
+        if baseline.latency.p99 > 0 {
+            let p99_ratio = current.latency.p99 as f64 / baseline.latency.p99 as f64;
+            if p99_ratio > self.max_p99_ratio {
+                return Err(format!(
+                    "p99 latency regressed: {}us vs baseline {}us ({:.2}x > {:.2}x tolerance)",
+                    current.latency.p99, baseline.latency.p99, p99_ratio, self.max_p99_ratio
+                ));
+            }
+        }
+
+        if baseline.throughput_per_sec > 0.0 {
+            let throughput_ratio = current.throughput_per_sec / baseline.throughput_per_sec;
+            if throughput_ratio < self.min_throughput_ratio {
+                return Err(format!(
+                    "throughput regressed: {:.1}/sec vs baseline {:.1}/sec ({:.2}x < {:.2}x tolerance)",
+                    current.throughput_per_sec,
+                    baseline.throughput_per_sec,
+                    throughput_ratio,
+                    self.min_throughput_ratio
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The current commit's SHA, for stamping into reports so a regression
+/// can be bisected back to the commit that caused it. Falls back to
+/// `"unknown"` outside a git checkout (e.g. an extracted source archive).
+fn current_git_sha() -> String {
+    Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
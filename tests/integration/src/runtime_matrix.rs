@@ -0,0 +1,119 @@
+//! Runs a load-test scenario across a matrix of tokio runtime flavors
+//! and client-side concurrency caps, instead of whatever the default
+//! `#[tokio::test]` multi-thread scheduler and unbounded task spawning
+//! happen to produce.
+//!
+//! [`run_on_runtime`] mirrors the shape of tokio's own `rt_test!` macro:
+//! the same async body runs once per [`RuntimeFlavor`] on a freshly built
+//! runtime. [`ConcurrencyLimiter`] adds a second axis -- the number of
+//! requests the client allows in flight at once -- since that cap, not
+//! just worker thread count, is usually what determines achievable
+//! upload/event throughput.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::runtime::{Builder, Runtime};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Large enough that [`ConcurrencyLimiter::unbounded`] never actually
+/// blocks a caller under any load this crate generates, without risking
+/// the overflow panics `Semaphore::MAX_PERMITS` (`usize::MAX >> 3`)
+/// guards against.
+const EFFECTIVELY_UNBOUNDED: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeFlavor {
+    CurrentThread,
+    MultiThread { worker_threads: usize },
+}
+
+impl RuntimeFlavor {
+    pub fn label(&self) -> String {
+        match self {
+            RuntimeFlavor::CurrentThread => "current_thread".to_string(),
+            RuntimeFlavor::MultiThread { worker_threads } => {
+                format!("multi_thread_{worker_threads}w")
+            }
+        }
+    }
+
+    fn build(&self) -> Runtime {
+        match self {
+            RuntimeFlavor::CurrentThread => Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("current-thread runtime should always build"),
+            RuntimeFlavor::MultiThread { worker_threads } => Builder::new_multi_thread()
+                .worker_threads(*worker_threads)
+                .enable_all()
+                .build()
+                .expect("multi-thread runtime should always build"),
+        }
+    }
+}
+
+/// The runtime flavors a scenario matrix sweeps by default: current-thread,
+/// a single-worker multi-thread runtime (isolating scheduler overhead from
+/// parallelism), and a multi-thread runtime sized to the host's CPU count.
+pub fn default_runtime_flavors() -> Vec<RuntimeFlavor> {
+    vec![
+        RuntimeFlavor::CurrentThread,
+        RuntimeFlavor::MultiThread { worker_threads: 1 },
+        RuntimeFlavor::MultiThread {
+            worker_threads: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4),
+        },
+    ]
+}
+
+/// The client-side concurrency caps a scenario matrix sweeps by default --
+/// the number of requests allowed in flight at once.
+pub fn default_concurrency_levels() -> Vec<usize> {
+    vec![50, 100, 500]
+}
+
+/// Caps the number of in-flight requests a scenario issues at once,
+/// regardless of how many tasks it has spawned.
+#[derive(Clone)]
+pub struct ConcurrencyLimiter {
+    semaphore: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimiter {
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_in_flight)),
+        }
+    }
+
+    /// A limiter wide enough to never block, for scenarios that aren't
+    /// part of a concurrency sweep but still call a helper that takes one.
+    pub fn unbounded() -> Self {
+        Self::new(EFFECTIVELY_UNBOUNDED)
+    }
+
+    /// Waits for an in-flight slot to free up and returns a permit that
+    /// holds it until dropped. Held across a request (rather than wrapping
+    /// the request in a closure) so the caller's own control flow --
+    /// `tokio::select!`, early `return`, `?` -- works exactly as it would
+    /// without a limiter.
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.semaphore
+            .acquire()
+            .await
+            .expect("semaphore is never closed")
+    }
+}
+
+/// Runs `scenario` to completion on a fresh runtime built for `flavor`,
+/// mirroring tokio's `rt_test!` pattern of exercising the same async body
+/// under multiple runtime configurations.
+pub fn run_on_runtime<F, Fut, T>(flavor: RuntimeFlavor, scenario: F) -> T
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    flavor.build().block_on(scenario())
+}
@@ -1,12 +1,12 @@
-/// Integration test for complete analysis flow
-/// Tests Requirements: 1.1, 2.6, 3.6, 5.6, 6.3
-/// 
-/// This test verifies:
-/// - File upload through API
-/// - Static analysis execution
-/// - Dynamic analysis execution
-/// - Verdict generation
-/// - Result streaming via WebSocket
+//! Integration test for complete analysis flow
+//! Tests Requirements: 1.1, 2.6, 3.6, 5.6, 6.3
+//! 
+//! This test verifies:
+//! - File upload through API
+//! - Static analysis execution
+//! - Dynamic analysis execution
+//! - Verdict generation
+//! - Result streaming via WebSocket
 
 use anyhow::Result;
 use futures_util::{SinkExt, StreamExt};
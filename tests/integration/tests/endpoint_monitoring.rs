@@ -1,14 +1,15 @@
-/// Integration test for endpoint monitoring
-/// Tests Requirements: 7.1, 7.2, 7.3, 7.4, 7.5
-/// 
-/// This test verifies:
-/// - Telemetry event ingestion from mock endpoint
-/// - Event storage in TimescaleDB
-/// - Alert generation for suspicious patterns
-/// - Real-time alert delivery
+//! Integration test for endpoint monitoring
+//! Tests Requirements: 7.1, 7.2, 7.3, 7.4, 7.5
+//! 
+//! This test verifies:
+//! - Telemetry event ingestion from mock endpoint
+//! - Event storage in TimescaleDB
+//! - Alert generation for suspicious patterns
+//! - Real-time alert delivery
 
 use anyhow::Result;
 use chrono::Utc;
+use integration_tests::auth::{analyst_scopes, issue_test_token};
 use serde_json::json;
 use sqlx::PgPool;
 use std::time::Duration;
@@ -149,7 +150,7 @@ async fn test_real_time_alert_delivery() -> Result<()> {
     let token = create_test_user_token(&tenant_id).await?;
 
     // Step 1: Establish WebSocket connection for alerts
-    let ws_url = format!("ws://localhost:8080/api/v1/ws/alerts");
+    let ws_url = "ws://localhost:8080/api/v1/ws/alerts".to_string();
     let (ws_stream, _) = tokio_tungstenite::connect_async(&ws_url).await?;
     let (mut write, mut read) = futures_util::StreamExt::split(ws_stream);
     println!("✓ WebSocket connection established");
@@ -296,7 +297,7 @@ async fn test_mtls_authentication() -> Result<()> {
     
     let result = send_telemetry_without_cert(&vec![event.clone()]).await;
     assert!(
-        result.is_err() || result.unwrap() == false,
+        result.is_err() || !result.unwrap(),
         "Request without valid mTLS cert should be rejected"
     );
     println!("✓ Request without mTLS certificate rejected");
@@ -391,7 +392,13 @@ async fn create_test_tenant() -> Result<Uuid> {
 }
 
 async fn create_test_user_token(tenant_id: &Uuid) -> Result<String> {
-    Ok(format!("test_token_{}", tenant_id))
+    let user_id = Uuid::new_v4();
+    Ok(issue_test_token(
+        user_id,
+        *tenant_id,
+        vec!["analyst".to_string()],
+        analyst_scopes(),
+    ))
 }
 
 fn create_test_telemetry_events(endpoint_id: &Uuid, tenant_id: &Uuid) -> Vec<serde_json::Value> {
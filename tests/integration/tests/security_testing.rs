@@ -1,14 +1,17 @@
-/// Integration test for security testing
-/// Tests Requirements: 8.5, 9.5, 10.2, 20.3, 11.5
-/// 
-/// This test verifies:
-/// - SQL injection attempts are blocked
-/// - Cross-tenant access attempts are rejected
-/// - JWT token manipulation is detected
-/// - Sandbox escape attempts are prevented
-/// - Rate limit bypass attempts are blocked
+//! Integration test for security testing
+//! Tests Requirements: 8.5, 9.5, 10.2, 20.3, 11.5
+//! 
+//! This test verifies:
+//! - SQL injection attempts are blocked
+//! - Cross-tenant access attempts are rejected
+//! - JWT token manipulation is detected
+//! - Sandbox escape attempts are prevented
+//! - Rate limit bypass attempts are blocked
 
 use anyhow::Result;
+use integration_tests::auth::{
+    analyst_scopes, issue_expired_test_token, issue_partial_test_token, issue_test_token,
+};
 use reqwest::multipart;
 use serde_json::json;
 use std::time::Duration;
@@ -24,7 +27,7 @@ async fn test_sql_injection_prevention() -> Result<()> {
     let token = authenticate_test_user().await?;
     
     // Test various SQL injection payloads
-    let injection_payloads = vec![
+    let injection_payloads = [
         "' OR '1'='1",
         "'; DROP TABLE artifacts; --",
         "' UNION SELECT * FROM users --",
@@ -126,6 +129,31 @@ async fn test_cross_tenant_access_prevention() -> Result<()> {
     }
     println!("✓ Artifact listing properly isolated");
     
+    // Attempt 6: BOLA scanner against the tenant-settings endpoint, both
+    // identities against each other's tenant and a few guessed neighbors
+    let http = reqwest::Client::new();
+    let identity1 = integration_tests::bola::Identity {
+        token: user1_token.clone(),
+        tenant_id: tenant1_id,
+    };
+    let identity2 = integration_tests::bola::Identity {
+        token: user2_token.clone(),
+        tenant_id: tenant2_id,
+    };
+    let mut bola_findings =
+        integration_tests::bola::scan_tenant_settings_with_neighbors(&http, &identity1, tenant2_id)
+            .await?;
+    bola_findings.extend(
+        integration_tests::bola::scan_tenant_settings_with_neighbors(&http, &identity2, tenant1_id)
+            .await?,
+    );
+    assert!(
+        bola_findings.is_empty(),
+        "BOLA scan found cross-tenant access: {:?}",
+        bola_findings
+    );
+    println!("✓ BOLA scan found no cross-tenant settings access");
+    
     println!("\n✅ Cross-tenant access prevention test PASSED");
     Ok(())
 }
@@ -174,10 +202,91 @@ async fn test_jwt_token_manipulation() -> Result<()> {
     assert!(result.is_err(), "Revoked token should be rejected");
     println!("✓ Revoked token rejected");
     
+    // Test 7: Partial (pre-MFA) token rejected for a privileged action
+    let partial_token = issue_partial_test_token(
+        Uuid::new_v4(),
+        tenant_id,
+        vec!["analyst".to_string()],
+    );
+    let result = upload_binary(&partial_token, "probe.exe", vec![0x4D, 0x5A]).await;
+    assert!(
+        result.is_err(),
+        "A partial token issued before MFA completion should be rejected for a privileged action"
+    );
+    println!("✓ Partial (pre-MFA) token rejected");
+    
     println!("\n✅ JWT token manipulation test PASSED");
     Ok(())
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_role_propagation_convergence() -> Result<()> {
+    println!("Starting role-propagation convergence test...");
+
+    let tenant_id = create_test_tenant("Propagation Test Tenant").await?;
+    let admin_token = create_user_with_role(&tenant_id, "admin@test.com", "admin").await?;
+    let analyst_token = create_user_with_role(&tenant_id, "analyst@test.com", "analyst").await?;
+
+    // Grant, then immediately revoke, the analyst's settings-management
+    // capability, mirroring a role downgrade an operator might push.
+    set_permission_overwrite(
+        &admin_token,
+        &tenant_id,
+        &integration_tests::acl::Overwrite::new(integration_tests::acl::SubjectType::Role, "analyst")
+            .allowing(integration_tests::acl::Capability::MANAGE_SETTINGS),
+    )
+    .await?;
+    set_permission_overwrite(
+        &admin_token,
+        &tenant_id,
+        &integration_tests::acl::Overwrite::new(integration_tests::acl::SubjectType::Role, "analyst")
+            .denying(integration_tests::acl::Capability::MANAGE_SETTINGS),
+    )
+    .await?;
+
+    // The cluster's node list -- in this environment a single node, but
+    // the same probe is meant to run against every querier/ingestor base
+    // URL in a real multi-node deployment.
+    let nodes = vec![API_BASE_URL.to_string()];
+    let check_token = analyst_token.clone();
+    let results = integration_tests::convergence::check_convergence(
+        &nodes,
+        Duration::from_secs(10),
+        Duration::from_millis(200),
+        move |node_base_url| {
+            let token = check_token.clone();
+            async move {
+                let client = reqwest::Client::new();
+                let response = client
+                    .patch(format!("{}/api/v1/tenants/{}/settings", node_base_url, tenant_id))
+                    .header("Authorization", format!("Bearer {}", token))
+                    .json(&json!({ "setting": "value" }))
+                    .send()
+                    .await?;
+                Ok(!response.status().is_success())
+            }
+        },
+    )
+    .await?;
+
+    let lagging = integration_tests::convergence::lagging_nodes(&results);
+    assert!(
+        lagging.is_empty(),
+        "Revoked permission still honored past the convergence window on: {:?}",
+        lagging
+    );
+
+    for result in &results {
+        if let Some(after) = result.converged_after {
+            println!("  ✓ {} converged after {:?}", result.node_base_url, after);
+        }
+    }
+
+    println!("\n✅ Role propagation convergence test PASSED");
+    Ok(())
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_sandbox_escape_prevention() -> Result<()> {
@@ -201,7 +310,7 @@ async fn test_sandbox_escape_prevention() -> Result<()> {
         tokio::time::sleep(Duration::from_secs(30)).await;
         
         // Verify sandbox remained isolated
-        let sandbox_status = check_sandbox_integrity(&artifact_id).await?;
+        let sandbox_status = check_sandbox_integrity(&token, &artifact_id).await?;
         assert!(
             sandbox_status["isolated"].as_bool().unwrap_or(false),
             "Sandbox should remain isolated for {}",
@@ -211,7 +320,7 @@ async fn test_sandbox_escape_prevention() -> Result<()> {
         // Verify no host system compromise
         let host_status = check_host_system_integrity().await?;
         assert!(
-            host_status["compromised"].as_bool().unwrap_or(true) == false,
+            !host_status["compromised"].as_bool().unwrap_or(true),
             "Host system should not be compromised"
         );
         
@@ -223,7 +332,7 @@ async fn test_sandbox_escape_prevention() -> Result<()> {
     println!("✓ Seccomp filters active");
     
     // Verify network isolation
-    verify_network_isolation().await?;
+    verify_network_isolation(&token).await?;
     println!("✓ Network isolation enforced");
     
     // Verify resource limits enforced
@@ -239,7 +348,8 @@ async fn test_sandbox_escape_prevention() -> Result<()> {
 async fn test_rate_limit_enforcement() -> Result<()> {
     println!("Starting rate limit enforcement test...");
 
-    let token = authenticate_test_user().await?;
+    let tenant_id = create_test_tenant("Rate Limit Test Tenant").await?;
+    let token = create_user_token(&tenant_id, "user@test.com").await?;
     
     // Test 1: API rate limiting (1000 requests/min per tenant - Requirement 11.5)
     let rate_limit = 1000;
@@ -301,7 +411,7 @@ async fn test_rate_limit_enforcement() -> Result<()> {
     println!("  Testing rate limit bypass attempts...");
     
     // Attempt 1: Multiple tokens from same tenant
-    let token2 = create_user_token_same_tenant(&token).await?;
+    let token2 = create_user_token_same_tenant(&tenant_id).await?;
     let bypass_result = attempt_rate_limit_bypass_with_multiple_tokens(&token, &token2).await;
     assert!(
         !bypass_result,
@@ -351,11 +461,30 @@ async fn test_authorization_bypass_attempts() -> Result<()> {
     );
     println!("✓ Analyst privilege escalation blocked");
     
-    // Test 3: Role manipulation in request
-    let result = make_request_with_role_override(&viewer_token, "admin").await;
+    // Test 3: Role manipulation via header-injection matrix
+    let escalation_targets = vec![
+        integration_tests::escalation::Target {
+            method: reqwest::Method::GET,
+            path: "/api/v1/dashboard/stats".to_string(),
+            severity: integration_tests::escalation::Severity::Read,
+        },
+        integration_tests::escalation::Target {
+            method: reqwest::Method::PATCH,
+            path: format!("/api/v1/tenants/{}/settings", tenant_id),
+            severity: integration_tests::escalation::Severity::Write,
+        },
+    ];
+    let escalation_findings = integration_tests::escalation::scan(
+        &reqwest::Client::new(),
+        API_BASE_URL,
+        &viewer_token,
+        &escalation_targets,
+    )
+    .await?;
     assert!(
-        result.is_err(),
-        "Role override in request should be rejected"
+        escalation_findings.is_empty(),
+        "Role override headers should never change a response: {:?}",
+        escalation_findings
     );
     println!("✓ Role override blocked");
     
@@ -364,6 +493,63 @@ async fn test_authorization_bypass_attempts() -> Result<()> {
     assert!(result.is_ok(), "Admin should be able to modify settings");
     println!("✓ Admin privileges work correctly");
     
+    // Test 5: Permission overwrite allow/deny drift detection
+    let capability = integration_tests::acl::Capability::DELETE_ARTIFACT;
+    let mut drift_findings = Vec::new();
+
+    let deny_spec = integration_tests::acl::OverwriteSpec {
+        role: Some(
+            integration_tests::acl::Overwrite::new(integration_tests::acl::SubjectType::Role, "viewer")
+                .denying(capability),
+        ),
+        user: None,
+    };
+    drift_findings.extend(
+        run_drift_case(
+            &admin_token,
+            &viewer_token,
+            &tenant_id,
+            "explicit deny refuses the action",
+            capability,
+            deny_spec,
+        )
+        .await?,
+    );
+
+    let allow_spec = integration_tests::acl::OverwriteSpec {
+        role: Some(
+            integration_tests::acl::Overwrite::new(integration_tests::acl::SubjectType::Role, "viewer")
+                .allowing(capability),
+        ),
+        user: None,
+    };
+    drift_findings.extend(
+        run_drift_case(
+            &admin_token,
+            &viewer_token,
+            &tenant_id,
+            "explicit allow grants the action",
+            capability,
+            allow_spec,
+        )
+        .await?,
+    );
+
+    for (case, spec) in
+        integration_tests::acl::standard_conflict_cases("viewer", "viewer-user", capability)
+    {
+        drift_findings.extend(
+            run_drift_case(&admin_token, &viewer_token, &tenant_id, case, capability, spec).await?,
+        );
+    }
+
+    assert!(
+        drift_findings.is_empty(),
+        "Permission overwrite drift detected: {:?}",
+        drift_findings
+    );
+    println!("✓ No permission overwrite drift detected");
+    
     println!("\n✅ Authorization bypass test PASSED");
     Ok(())
 }
@@ -395,8 +581,13 @@ async fn create_test_tenant(name: &str) -> Result<Uuid> {
     Ok(tenant_id)
 }
 
-async fn create_user_token(tenant_id: &Uuid, email: &str) -> Result<String> {
-    Ok(format!("token_{}_{}", tenant_id, email))
+async fn create_user_token(tenant_id: &Uuid, _email: &str) -> Result<String> {
+    Ok(issue_test_token(
+        Uuid::new_v4(),
+        *tenant_id,
+        vec!["analyst".to_string()],
+        analyst_scopes(),
+    ))
 }
 
 async fn create_user_with_role(tenant_id: &Uuid, email: &str, role: &str) -> Result<String> {
@@ -462,7 +653,7 @@ async fn upload_artifact(token: &str, filename: &str) -> Result<Uuid> {
     let form = multipart::Form::new().part(
         "file",
         multipart::Part::bytes(vec![0x4D, 0x5A])
-            .file_name(filename)
+            .file_name(filename.to_string())
             .mime_str("application/x-msdownload")?,
     );
     
@@ -552,8 +743,13 @@ async fn list_artifacts(token: &str) -> Result<Vec<serde_json::Value>> {
     Ok(body["artifacts"].as_array().unwrap_or(&vec![]).clone())
 }
 
-async fn create_expired_token(_tenant_id: &Uuid) -> Result<String> {
-    Ok("expired_token".to_string())
+async fn create_expired_token(tenant_id: &Uuid) -> Result<String> {
+    Ok(issue_expired_test_token(
+        Uuid::new_v4(),
+        *tenant_id,
+        vec!["analyst".to_string()],
+        analyst_scopes(),
+    ))
 }
 
 fn tamper_with_token_signature(token: &str) -> String {
@@ -572,7 +768,19 @@ fn create_token_without_tenant_id() -> String {
     "incomplete_token".to_string()
 }
 
-async fn revoke_token(_token: &str) -> Result<()> {
+async fn revoke_token(token: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("{}/api/v1/auth/logout", API_BASE_URL))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to revoke token: {}", response.status());
+    }
+
     Ok(())
 }
 
@@ -610,7 +818,7 @@ async fn upload_binary(token: &str, filename: &str, binary: Vec<u8>) -> Result<U
     let form = multipart::Form::new().part(
         "file",
         multipart::Part::bytes(binary)
-            .file_name(filename)
+            .file_name(filename.to_string())
             .mime_str("application/x-msdownload")?,
     );
     
@@ -625,8 +833,25 @@ async fn upload_binary(token: &str, filename: &str, binary: Vec<u8>) -> Result<U
     Ok(Uuid::parse_str(body["artifact_id"].as_str().unwrap())?)
 }
 
-async fn check_sandbox_integrity(_artifact_id: &Uuid) -> Result<serde_json::Value> {
-    Ok(json!({ "isolated": true }))
+async fn check_sandbox_integrity(token: &str, artifact_id: &Uuid) -> Result<serde_json::Value> {
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{}/api/v1/artifacts/{}/network-log", API_BASE_URL, artifact_id))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("network log request failed: {}", response.status());
+    }
+
+    let events: Vec<serde_json::Value> = response.json().await?;
+    // Isolated means nothing the sandbox tried reached real infrastructure --
+    // every recorded event must have been sinkholed rather than allowed through.
+    let isolated = events.iter().all(|e| e["sinkholed"].as_bool().unwrap_or(false));
+
+    Ok(json!({ "isolated": isolated }))
 }
 
 async fn check_host_system_integrity() -> Result<serde_json::Value> {
@@ -637,7 +862,29 @@ async fn verify_seccomp_filters_active() -> Result<()> {
     Ok(())
 }
 
-async fn verify_network_isolation() -> Result<()> {
+async fn verify_network_isolation(token: &str) -> Result<()> {
+    // Uploads its own probe artifact rather than reusing one from the escape
+    // attempts above, so this assertion does not depend on the loop above
+    // having run first.
+    let probe_id = upload_binary(token, "network_probe.bin", vec![0x00]).await?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("{}/api/v1/artifacts/{}/network-log", API_BASE_URL, probe_id))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("network log request failed: {}", response.status());
+    }
+
+    let events: Vec<serde_json::Value> = response.json().await?;
+    assert!(
+        events.iter().all(|e| e["sinkholed"].as_bool().unwrap_or(false)),
+        "every recorded network event must have been sinkholed under the default-deny policy"
+    );
+
     Ok(())
 }
 
@@ -693,16 +940,36 @@ async fn upload_small_file(token: &str, id: usize) -> Result<()> {
     Ok(())
 }
 
-async fn create_user_token_same_tenant(_token: &str) -> Result<String> {
-    Ok("token2_same_tenant".to_string())
+async fn create_user_token_same_tenant(tenant_id: &Uuid) -> Result<String> {
+    Ok(issue_test_token(
+        Uuid::new_v4(),
+        *tenant_id,
+        vec!["analyst".to_string()],
+        analyst_scopes(),
+    ))
 }
 
-async fn attempt_rate_limit_bypass_with_multiple_tokens(_token1: &str, _token2: &str) -> bool {
-    false
+// token1 already drove the tenant past its limit earlier in the test; a
+// distinct token for the same tenant should be rate-limited right along
+// with it, since the limiter is keyed on tenant_id rather than on the
+// token presented.
+async fn attempt_rate_limit_bypass_with_multiple_tokens(_token1: &str, token2: &str) -> bool {
+    make_api_request(token2).await.is_ok()
 }
 
-async fn attempt_rate_limit_bypass_with_ip_rotation(_token: &str) -> bool {
-    false
+// A literal IP rotation isn't controllable from this test process; a
+// fresh client (a distinct connection) stands in for it, since the
+// limiter never keys on source address in the first place -- a real
+// rotation would be blocked the same way.
+async fn attempt_rate_limit_bypass_with_ip_rotation(token: &str) -> bool {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("{}/api/v1/dashboard/stats", API_BASE_URL))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await;
+
+    matches!(response, Ok(r) if r.status().is_success())
 }
 
 async fn delete_artifact(token: &str, artifact_id: &Uuid) -> Result<serde_json::Value> {
@@ -738,19 +1005,73 @@ async fn modify_tenant_settings(token: &str, tenant_id: &Uuid) -> Result<serde_j
     Ok(response.json().await?)
 }
 
-async fn make_request_with_role_override(token: &str, _role: &str) -> Result<()> {
+async fn set_permission_overwrite(
+    token: &str,
+    tenant_id: &Uuid,
+    overwrite: &integration_tests::acl::Overwrite,
+) -> Result<()> {
     let client = reqwest::Client::new();
-    
+
+    let subject_type = match overwrite.subject_type {
+        integration_tests::acl::SubjectType::Role => "role",
+        integration_tests::acl::SubjectType::User => "user",
+    };
+
     let response = client
-        .get(format!("{}/api/v1/dashboard/stats", API_BASE_URL))
+        .patch(format!("{}/api/v1/tenants/{}/settings", API_BASE_URL, tenant_id))
         .header("Authorization", format!("Bearer {}", token))
-        .header("X-Role-Override", "admin")
+        .json(&json!({
+            "permission_overwrites": [{
+                "subject_type": subject_type,
+                "subject_id": overwrite.subject_id,
+                "allow": overwrite.allow,
+                "deny": overwrite.deny,
+            }]
+        }))
         .send()
         .await?;
-    
+
     if !response.status().is_success() {
-        anyhow::bail!("Request failed");
+        anyhow::bail!("Storing permission overwrite failed");
     }
-    
+
     Ok(())
 }
+
+async fn attempt_delete_artifact(token: &str) -> Result<bool> {
+    let client = reqwest::Client::new();
+
+    let response = client
+        .delete(format!("{}/api/v1/artifacts/{}", API_BASE_URL, Uuid::new_v4()))
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await?;
+
+    Ok(response.status().is_success())
+}
+
+async fn run_drift_case(
+    admin_token: &str,
+    viewer_token: &str,
+    tenant_id: &Uuid,
+    case: &'static str,
+    capability: integration_tests::acl::Capability,
+    spec: integration_tests::acl::OverwriteSpec,
+) -> Result<Option<integration_tests::acl::DriftFinding>> {
+    integration_tests::acl::assert_enforced(
+        case,
+        capability,
+        &spec,
+        || async {
+            if let Some(overwrite) = &spec.role {
+                set_permission_overwrite(admin_token, tenant_id, overwrite).await?;
+            }
+            if let Some(overwrite) = &spec.user {
+                set_permission_overwrite(admin_token, tenant_id, overwrite).await?;
+            }
+            Ok(())
+        },
+        || attempt_delete_artifact(viewer_token),
+    )
+    .await
+}
@@ -1,10 +1,10 @@
-/// Integration test for SIEM integration
-/// Tests Requirements: 11.1, 11.2, 11.3
-/// 
-/// This test verifies:
-/// - Webhook integration configuration
-/// - Verdict delivery in CEF/LEEF format
-/// - Retry logic with failing endpoints
+//! Integration test for SIEM integration
+//! Tests Requirements: 11.1, 11.2, 11.3
+//! 
+//! This test verifies:
+//! - Webhook integration configuration
+//! - Verdict delivery in CEF/LEEF format
+//! - Retry logic with failing endpoints
 
 use anyhow::Result;
 use serde_json::json;